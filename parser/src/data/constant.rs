@@ -0,0 +1,7 @@
+use crate::data::Value;
+
+#[derive(Debug, Clone)]
+pub struct Constant {
+    pub name: String,
+    pub value: Value,
+}
@@ -1,4 +1,16 @@
 #[derive(Debug, Clone)]
 pub struct Import {
     pub file: String,
+    pub items: ImportItems,
+}
+
+/// Names an import makes visible in the importing file.
+#[derive(Debug, Clone)]
+pub enum ImportItems {
+    /// `import "file"`: every definition of the file, unqualified.
+    All,
+    /// `import "file" as ns`: every definition of the file, as `ns.Name`.
+    Namespace(String),
+    /// `import { A, B } from "file"`: the listed definitions, unqualified.
+    Selected(Vec<String>),
 }
@@ -7,5 +7,7 @@ pub struct Info {
     pub description: Option<String>,
     pub terms_of_service: Option<String>,
     pub version: Option<String>,
+    /// Names of the servers the document lists; all of them when `None`.
+    pub servers: Option<Vec<String>>,
     pub base: Option<String>,
 }
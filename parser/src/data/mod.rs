@@ -1,5 +1,6 @@
 use std::fmt::Debug;
 
+pub use constant::*;
 pub use import::*;
 pub use info::*;
 pub use parameter::*;
@@ -7,8 +8,12 @@ pub use path::*;
 pub use request_body::*;
 pub use response::*;
 pub use schema::*;
+pub use security::*;
+pub use server::*;
+pub use span::*;
 pub use tag::*;
 
+mod constant;
 mod import;
 mod info;
 mod parameter;
@@ -16,7 +21,11 @@ mod path;
 mod request_body;
 mod response;
 mod schema;
+mod security;
+mod server;
+mod span;
 mod tag;
+pub mod visit;
 
 #[derive(Debug, Clone)]
 pub enum ReferenceOr<T>
@@ -32,20 +41,25 @@ pub enum Object {
     Schema(Schema),
     Tag(Tag),
     Response(Response),
-    Path(Path),
+    Path(Box<Path>),
     RequestBody(RequestBody),
     Enum(Enum),
     Info(Info),
+    Constant(Constant),
+    Server(Server),
+    SecurityScheme(SecurityScheme),
 }
 
 #[derive(Debug, Clone)]
 pub enum Value {
     Immediate(Literal),
     Identifier(String),
+    List(Vec<Value>),
+    Object(Vec<(String, Value)>),
 }
 
 #[derive(Debug, Clone)]
 pub struct SourceFileContent {
-    pub imports: Vec<Import>,
-    pub objects: Vec<Object>,
+    pub imports: Vec<Spanned<Import>>,
+    pub objects: Vec<Spanned<Object>>,
 }
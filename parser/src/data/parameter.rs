@@ -1,4 +1,4 @@
-use crate::data::TypeWithAttributes;
+use crate::data::{Span, TypeWithAttributes};
 use crate::{Attributes, Requirement};
 
 #[derive(Debug, Clone, Default)]
@@ -9,6 +9,8 @@ pub struct Parameters {
 #[derive(Debug, Clone)]
 pub struct Parameter {
     pub name: String,
+    pub span: Span,
+    pub doc: Option<String>,
     pub ty: Option<ParameterType>,
     pub content: ParameterContent,
 }
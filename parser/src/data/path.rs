@@ -1,9 +1,9 @@
-use std::collections::HashMap;
+use indexmap::IndexMap;
 use std::fmt::Debug;
 
 use crate::data::parameter::Parameters;
 use crate::data::response::ResponseContent;
-use crate::data::{Attributes, RequestBodyContent};
+use crate::data::{Attributes, RequestBodyContent, Span};
 use crate::ReferenceOr;
 
 #[derive(Debug, Clone)]
@@ -26,9 +26,41 @@ pub struct PathContent {
     pub trace: Option<Operation>,
 }
 
+impl PathContent {
+    /// The operations defined for the path.
+    pub fn operations(&self) -> impl Iterator<Item = &Operation> {
+        [
+            &self.get,
+            &self.post,
+            &self.put,
+            &self.delete,
+            &self.options,
+            &self.head,
+            &self.patch,
+            &self.trace,
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    pub fn operations_mut(&mut self) -> impl Iterator<Item = &mut Operation> {
+        [
+            &mut self.get,
+            &mut self.post,
+            &mut self.put,
+            &mut self.delete,
+            &mut self.options,
+            &mut self.head,
+            &mut self.patch,
+            &mut self.trace,
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum HttpMethod {
-    Default,
     Get,
     Post,
     Put,
@@ -42,14 +74,17 @@ pub enum HttpMethod {
 #[derive(Debug, Clone)]
 pub struct Operation {
     pub name: String,
+    pub span: Span,
+    pub doc: Option<String>,
     pub method: HttpMethod,
     pub parameters: Parameters,
     pub content: OperationContent,
     pub request_body: Option<ReferenceOr<RequestBodyContent>>,
+    pub attributes: Attributes,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct OperationContent {
     pub default: Option<ReferenceOr<ResponseContent>>,
-    pub response: HashMap<String, ReferenceOr<ResponseContent>>,
+    pub response: IndexMap<String, ReferenceOr<ResponseContent>>,
 }
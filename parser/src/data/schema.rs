@@ -1,11 +1,16 @@
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
-use crate::data::Value;
+use crate::data::{Span, Value};
 use crate::ReferenceOr;
 
 #[derive(Debug, Clone)]
 pub struct Schema {
     pub name: Option<String>,
+    pub doc: Option<String>,
+    /// Type parameters of a generic schema, as in `schema Page<T> { ... }`.
+    pub parameters: Vec<String>,
+    /// Schema whose fields this schema extends, as in `schema AdminUser extends User { ... }`.
+    pub base: Option<String>,
     pub content: SchemaContent,
 }
 
@@ -21,6 +26,8 @@ pub enum SchemaContent {
 #[derive(Debug, Clone)]
 pub struct SchemaField {
     pub name: String,
+    pub span: Span,
+    pub doc: Option<String>,
     pub requirement: Requirement,
     pub target_type: TypeWithAttributes,
 }
@@ -40,14 +47,45 @@ pub struct TypeWithAttributes {
 
 #[derive(Debug, Clone)]
 pub enum Type {
-    Integer { format: Option<String> },
-    String { format: Option<String> },
-    Float,
+    Integer {
+        format: Option<String>,
+    },
+    String {
+        format: Option<String>,
+    },
+    Number {
+        format: Option<String>,
+    },
     Bool,
-    List { item_type: Box<TypeWithAttributes> },
+    List {
+        item_type: Box<TypeWithAttributes>,
+    },
     Object,
     Schema(ReferenceOr<SchemaContent>),
+    /// A generic schema with its type arguments, written `Page<User>`.
+    Instance {
+        name: String,
+        arguments: Vec<TypeWithAttributes>,
+    },
+    /// A type parameter of the generic schema it appears in.
+    Parameter(String),
     Enum(EnumContent),
+    /// Exactly one of the types, written `A | B` or `oneOf<A, B>`.
+    OneOf(Vec<TypeWithAttributes>),
+    /// One or more of the types, written `anyOf<A, B>`.
+    AnyOf(Vec<TypeWithAttributes>),
+    /// All of the types, written `A & B` or `allOf<A, B>`.
+    AllOf(Vec<TypeWithAttributes>),
+    /// One of the schemas, told apart by the value of a property, written
+    /// `oneOf by "kind" { card: CardPayment }`.
+    Discriminated(DiscriminatedUnion),
+}
+
+#[derive(Debug, Clone)]
+pub struct DiscriminatedUnion {
+    pub property: String,
+    /// Values of the property and the names of the schemas they select, in order.
+    pub mapping: Vec<(String, String)>,
 }
 
 #[derive(Debug, Clone)]
@@ -56,23 +94,30 @@ pub enum Literal {
     Int(i64),
     Bool(bool),
     Float(f64),
-    List(Vec<Literal>),
+    Null,
 }
 
-pub type Attributes = HashMap<String, Value>;
+pub type Attributes = IndexMap<String, Value>;
 
 #[derive(Debug, Clone)]
 pub struct Enum {
     pub name: String,
+    pub doc: Option<String>,
     pub content: EnumContent,
 }
 
 #[derive(Debug, Clone)]
 pub struct EnumContent {
-    pub selection: Vec<String>,
+    pub selection: Vec<EnumMember>,
     pub attributes: Attributes,
 }
 
+#[derive(Debug, Clone)]
+pub struct EnumMember {
+    pub name: String,
+    pub doc: Option<String>,
+}
+
 impl Type {
     pub(crate) fn int32() -> Self {
         Type::Integer {
@@ -86,6 +131,22 @@ impl Type {
         }
     }
 
+    pub(crate) fn number() -> Self {
+        Type::Number { format: None }
+    }
+
+    pub(crate) fn float() -> Self {
+        Type::Number {
+            format: Some("float".to_string()),
+        }
+    }
+
+    pub(crate) fn double() -> Self {
+        Type::Number {
+            format: Some("double".to_string()),
+        }
+    }
+
     pub(crate) fn string() -> Self {
         Type::String { format: None }
     }
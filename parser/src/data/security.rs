@@ -0,0 +1,33 @@
+use std::fmt::{Display, Formatter};
+
+use crate::data::Attributes;
+
+#[derive(Debug, Clone)]
+pub struct SecurityScheme {
+    pub name: String,
+    /// Whether the scheme is required by every operation that does not say otherwise.
+    pub is_default: bool,
+    pub doc: Option<String>,
+    pub scheme_type: SecuritySchemeType,
+    /// Fields of the scheme, with nested blocks such as `flows` as objects.
+    pub fields: Attributes,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SecuritySchemeType {
+    Http,
+    ApiKey,
+    OAuth2,
+    OpenIdConnect,
+}
+
+impl Display for SecuritySchemeType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SecuritySchemeType::Http => "http",
+            SecuritySchemeType::ApiKey => "apiKey",
+            SecuritySchemeType::OAuth2 => "oauth2",
+            SecuritySchemeType::OpenIdConnect => "openIdConnect",
+        })
+    }
+}
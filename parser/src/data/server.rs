@@ -0,0 +1,19 @@
+use indexmap::IndexMap;
+
+use crate::data::Attributes;
+
+#[derive(Debug, Clone)]
+pub struct Server {
+    pub name: String,
+    pub url: String,
+    pub description: Option<String>,
+    pub variables: IndexMap<String, ServerVariable>,
+    pub attributes: Attributes,
+}
+
+/// Variable substituted for `{name}` in the URL of a server.
+#[derive(Debug, Clone)]
+pub struct ServerVariable {
+    pub default: String,
+    pub attributes: Attributes,
+}
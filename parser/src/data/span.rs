@@ -0,0 +1,77 @@
+use std::fmt::{Display, Formatter};
+
+/// 1-based line and column (in characters) of a byte offset in a source file.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Span {
+    pub file: String,
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(Debug, Clone)]
+pub struct Spanned<T> {
+    pub span: Span,
+    pub value: T,
+}
+
+/// Start offsets of the lines of a source file, to find the line and column of an offset
+/// without scanning the source up to it.
+#[derive(Debug, Clone)]
+pub(crate) struct LineIndex {
+    file: String,
+    source: String,
+    starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub(crate) fn new(file: &str, source: &str) -> Self {
+        LineIndex {
+            file: file.to_string(),
+            source: source.to_string(),
+            starts: std::iter::once(0)
+                .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+                .collect(),
+        }
+    }
+
+    /// Span from the byte offset `start` to `end`.
+    pub(crate) fn span(&self, start: usize, end: usize) -> Span {
+        Span {
+            file: self.file.clone(),
+            start: self.position(start),
+            end: self.position(end),
+        }
+    }
+
+    /// Span of the input parsed between the rests `before` and `after`.
+    pub(crate) fn span_between(&self, before: &str, after: &str) -> Span {
+        self.span(
+            self.source.len() - before.len(),
+            self.source.len() - after.len(),
+        )
+    }
+
+    fn position(&self, offset: usize) -> Position {
+        let line = self.starts.partition_point(|start| *start <= offset);
+        let line_start = self.starts[line - 1];
+
+        Position {
+            offset,
+            line,
+            column: self.source[line_start..offset].chars().count() + 1,
+        }
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.start.line, self.start.column)
+    }
+}
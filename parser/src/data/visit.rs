@@ -0,0 +1,206 @@
+use crate::data::{
+    Attributes, Object, Operation, Parameters, RequestBodyContent, ResponseContent, SchemaContent,
+    SchemaField, Type, TypeWithAttributes, Value,
+};
+use crate::ReferenceOr;
+
+/// What a name referenced from an object has to be defined as.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ReferenceKind {
+    /// A schema or enum, used as a type, extended or named in a discriminator mapping.
+    Schema,
+    Response,
+    RequestBody,
+    /// A server listed by an info config.
+    Server,
+    /// A name in an attribute or constant value, which can stand for any definition.
+    Value,
+}
+
+/// Walks the objects and everything in them.
+///
+/// Each method walks into the children of its node with the `walk_` function of the same
+/// name, so an implementation overrides the nodes it cares about and calls that function to
+/// keep going below them.
+pub trait VisitMut {
+    fn visit_object(&mut self, object: &mut Object) {
+        walk_object(self, object)
+    }
+
+    fn visit_operation(&mut self, op: &mut Operation) {
+        walk_operation(self, op)
+    }
+
+    fn visit_response_content(&mut self, r: &mut ResponseContent) {
+        walk_response_content(self, r)
+    }
+
+    fn visit_request_body_content(&mut self, r: &mut RequestBodyContent) {
+        walk_request_body_content(self, r)
+    }
+
+    fn visit_parameters(&mut self, ps: &mut Parameters) {
+        walk_parameters(self, ps)
+    }
+
+    fn visit_schema_content(&mut self, content: &mut SchemaContent) {
+        walk_schema_content(self, content)
+    }
+
+    fn visit_schema_field(&mut self, field: &mut SchemaField) {
+        walk_schema_field(self, field)
+    }
+
+    fn visit_type_with_attributes(&mut self, twa: &mut TypeWithAttributes) {
+        walk_type_with_attributes(self, twa)
+    }
+
+    fn visit_attributes(&mut self, attrs: &mut Attributes) {
+        walk_attributes(self, attrs)
+    }
+
+    fn visit_value(&mut self, v: &mut Value) {
+        walk_value(self, v)
+    }
+
+    fn visit_reference(&mut self, _name: &mut String, _kind: ReferenceKind) {}
+}
+
+pub fn walk_object<V: VisitMut + ?Sized>(v: &mut V, object: &mut Object) {
+    match object {
+        Object::Schema(s) => {
+            if let Some(base) = &mut s.base {
+                v.visit_reference(base, ReferenceKind::Schema);
+            }
+            v.visit_schema_content(&mut s.content);
+        }
+        Object::Enum(e) => v.visit_attributes(&mut e.content.attributes),
+        Object::Tag(t) => v.visit_attributes(&mut t.attributes),
+        Object::Response(r) => v.visit_response_content(&mut r.content),
+        Object::RequestBody(r) => v.visit_request_body_content(&mut r.content),
+        Object::Path(p) => {
+            v.visit_attributes(&mut p.attributes);
+            v.visit_parameters(&mut p.content.parameters);
+            for op in p.content.operations_mut() {
+                v.visit_operation(op);
+            }
+        }
+        Object::Constant(c) => v.visit_value(&mut c.value),
+        Object::Server(s) => {
+            v.visit_attributes(&mut s.attributes);
+            for variable in s.variables.values_mut() {
+                v.visit_attributes(&mut variable.attributes);
+            }
+        }
+        Object::SecurityScheme(s) => v.visit_attributes(&mut s.fields),
+        Object::Info(i) => {
+            for name in i.servers.iter_mut().flatten() {
+                v.visit_reference(name, ReferenceKind::Server);
+            }
+        }
+    }
+}
+
+pub fn walk_operation<V: VisitMut + ?Sized>(v: &mut V, op: &mut Operation) {
+    v.visit_attributes(&mut op.attributes);
+    v.visit_parameters(&mut op.parameters);
+    match &mut op.request_body {
+        None => {}
+        Some(ReferenceOr::Ref(name)) => v.visit_reference(name, ReferenceKind::RequestBody),
+        Some(ReferenceOr::Value(r)) => v.visit_request_body_content(r),
+    }
+    for r in op
+        .content
+        .default
+        .iter_mut()
+        .chain(op.content.response.values_mut())
+    {
+        match r {
+            ReferenceOr::Ref(name) => v.visit_reference(name, ReferenceKind::Response),
+            ReferenceOr::Value(r) => v.visit_response_content(r),
+        }
+    }
+}
+
+pub fn walk_response_content<V: VisitMut + ?Sized>(v: &mut V, r: &mut ResponseContent) {
+    v.visit_attributes(&mut r.attributes);
+    v.visit_parameters(&mut r.headers);
+    v.visit_type_with_attributes(&mut r.content);
+}
+
+pub fn walk_request_body_content<V: VisitMut + ?Sized>(v: &mut V, r: &mut RequestBodyContent) {
+    v.visit_attributes(&mut r.attributes);
+    v.visit_type_with_attributes(&mut r.content);
+}
+
+pub fn walk_parameters<V: VisitMut + ?Sized>(v: &mut V, ps: &mut Parameters) {
+    for p in &mut ps.parameters {
+        v.visit_attributes(&mut p.content.attributes);
+        v.visit_type_with_attributes(&mut p.content.content);
+    }
+}
+
+pub fn walk_schema_content<V: VisitMut + ?Sized>(v: &mut V, content: &mut SchemaContent) {
+    match content {
+        SchemaContent::Typedef(twa) => v.visit_type_with_attributes(twa),
+        SchemaContent::Definition { fields, attributes } => {
+            v.visit_attributes(attributes);
+            for f in fields {
+                v.visit_schema_field(f);
+            }
+        }
+    }
+}
+
+pub fn walk_schema_field<V: VisitMut + ?Sized>(v: &mut V, field: &mut SchemaField) {
+    v.visit_type_with_attributes(&mut field.target_type);
+}
+
+pub fn walk_type_with_attributes<V: VisitMut + ?Sized>(v: &mut V, twa: &mut TypeWithAttributes) {
+    v.visit_attributes(&mut twa.attributes);
+    match &mut twa.target_type {
+        Type::List { item_type } => v.visit_type_with_attributes(item_type),
+        Type::Schema(ReferenceOr::Ref(name)) => v.visit_reference(name, ReferenceKind::Schema),
+        Type::Schema(ReferenceOr::Value(content)) => v.visit_schema_content(content),
+        Type::Instance { name, arguments } => {
+            v.visit_reference(name, ReferenceKind::Schema);
+            for ty in arguments {
+                v.visit_type_with_attributes(ty);
+            }
+        }
+        Type::Enum(content) => v.visit_attributes(&mut content.attributes),
+        Type::OneOf(types) | Type::AnyOf(types) | Type::AllOf(types) => {
+            for ty in types {
+                v.visit_type_with_attributes(ty);
+            }
+        }
+        Type::Discriminated(union) => {
+            for (_, schema) in &mut union.mapping {
+                v.visit_reference(schema, ReferenceKind::Schema);
+            }
+        }
+        Type::Integer { .. }
+        | Type::String { .. }
+        | Type::Number { .. }
+        | Type::Bool
+        | Type::Object
+        | Type::Parameter(_) => {}
+    }
+}
+
+pub fn walk_attributes<V: VisitMut + ?Sized>(v: &mut V, attrs: &mut Attributes) {
+    for value in attrs.values_mut() {
+        v.visit_value(value);
+    }
+}
+
+pub fn walk_value<V: VisitMut + ?Sized>(v: &mut V, value: &mut Value) {
+    match value {
+        Value::Immediate(_) => {}
+        Value::Identifier(name) => v.visit_reference(name, ReferenceKind::Value),
+        Value::List(values) => values.iter_mut().for_each(|value| v.visit_value(value)),
+        Value::Object(entries) => entries
+            .iter_mut()
+            .for_each(|(_, value)| v.visit_value(value)),
+    }
+}
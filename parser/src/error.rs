@@ -0,0 +1,35 @@
+use std::fmt::{Display, Formatter};
+
+use crate::data::Span;
+
+/// Syntax error reported by [`crate::parse`].
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    // boxed to keep `Result<_, ParseError>` small
+    pub span: Box<Span>,
+    pub expected: Vec<String>,
+    pub found: String,
+    /// Set when the error is better explained by a message than by the expected tokens.
+    pub message: Option<String>,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(message) = &self.message {
+            return f.write_str(message);
+        }
+
+        match self.expected.as_slice() {
+            [] => write!(f, "unexpected {}", self.found),
+            [one] => write!(f, "expected {one}, found {}", self.found),
+            [init @ .., last] => write!(
+                f,
+                "expected one of {} or {last}, found {}",
+                init.join(", "),
+                self.found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
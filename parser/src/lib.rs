@@ -1,5 +1,7 @@
 mod data;
+mod error;
 mod parser;
 
 pub use data::*;
+pub use error::*;
 pub use parser::*;
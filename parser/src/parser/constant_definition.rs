@@ -0,0 +1,17 @@
+use nom::character::complete::char;
+use nom::combinator::cut;
+use nom::sequence::delimited;
+
+use crate::data::Constant;
+use crate::parser::definition_head;
+use crate::parser::error::IResult;
+use crate::parser::value::value;
+use crate::parser::whitespace::multispace0;
+
+pub(super) fn constant_definition(s: &str) -> IResult<&str, Constant> {
+    let (s, name) = definition_head("const")(s)?;
+    let (s, _) = cut(delimited(multispace0, char('='), multispace0))(s)?;
+    let (s, value) = cut(value)(s)?;
+
+    Ok((s, Constant { name, value }))
+}
@@ -1,23 +1,31 @@
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::{char, multispace0};
-use nom::combinator::opt;
-use nom::IResult;
+use nom::character::complete::char;
+use nom::combinator::{cut, opt};
 use nom::multi::separated_list0;
-use nom::sequence::delimited;
+use nom::sequence::{delimited, terminated};
 
 use crate::data::Enum;
-use crate::EnumContent;
-use crate::parser::{definition_head, shorthand_definition_head, wrapper};
+use crate::parser::error::IResult;
 use crate::parser::identifier::identifier;
 use crate::parser::literals::string_literal;
+use crate::parser::whitespace::{doc_comment, multispace0};
 use crate::parser::with_attributes::with_attributes;
+use crate::parser::{definition_head, shorthand_definition_head, wrapper};
+use crate::{EnumContent, EnumMember};
 
 pub(super) fn enum_definition(s: &str) -> IResult<&str, Enum> {
     let (s, name) = definition_head("enum")(s)?;
-    let (s, content) = enum_definition_impl(s)?;
+    let (s, content) = cut(enum_definition_impl)(s)?;
 
-    Ok((s, Enum { name, content }))
+    Ok((
+        s,
+        Enum {
+            name,
+            doc: None,
+            content,
+        },
+    ))
 }
 
 pub(super) fn shorthand_enum_definition(s: &str) -> IResult<&str, EnumContent> {
@@ -28,11 +36,8 @@ pub(super) fn shorthand_enum_definition(s: &str) -> IResult<&str, EnumContent> {
 fn enum_definition_impl(s: &str) -> IResult<&str, EnumContent> {
     let (s, attributes) = opt(with_attributes)(s)?;
     let (s, _) = multispace0(s)?;
-    let (s, selection) = delimited(
-        char('{'),
-        delimited(multispace0, enum_contents, multispace0),
-        char('}'),
-    )(s)?;
+    let (s, selection) =
+        delimited(char('{'), terminated(enum_contents, multispace0), char('}'))(s)?;
 
     Ok((
         s,
@@ -43,18 +48,18 @@ fn enum_definition_impl(s: &str) -> IResult<&str, EnumContent> {
     ))
 }
 
-fn enum_contents(s: &str) -> IResult<&str, Vec<String>> {
+fn enum_contents(s: &str) -> IResult<&str, Vec<EnumMember>> {
     let (s, c) = separated_list0(tag(","), enum_content)(s)?;
     let (s, _) = multispace0(s)?;
     let (s, _) = opt(char(','))(s)?;
 
-    Ok((s, c.into_iter().filter(|cc| !cc.is_empty()).collect()))
+    Ok((s, c.into_iter().filter(|cc| !cc.name.is_empty()).collect()))
 }
 
-fn enum_content(s: &str) -> IResult<&str, String> {
-    let (s, _) = multispace0(s)?;
-    let (s, c) = alt((wrapper(string_literal, |s| s.to_string()), identifier))(s)?;
+fn enum_content(s: &str) -> IResult<&str, EnumMember> {
+    let (s, doc) = doc_comment(s)?;
+    let (s, name) = alt((wrapper(string_literal, |s| s.to_string()), identifier))(s)?;
     let (s, _) = multispace0(s)?;
 
-    Ok((s, c))
+    Ok((s, EnumMember { name, doc }))
 }
@@ -0,0 +1,115 @@
+use nom::error::{ContextError, ErrorKind, FromExternalError, ParseError};
+use nom::InputLength;
+
+pub(crate) type IResult<I, O> = nom::IResult<I, O, SyntaxError<I>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expected {
+    Char(char),
+    Context(&'static str),
+    Kind(ErrorKind),
+}
+
+/// nom error which remembers what the parser was looking for.
+///
+/// When alternatives fail, the one that got furthest into the input wins,
+/// and alternatives failing at the same place are merged into one list.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SyntaxError<I> {
+    pub(crate) input: I,
+    pub(crate) expected: Vec<Expected>,
+    /// Replaces the "expected ..." description for errors which are not about the syntax itself.
+    pub(crate) message: Option<String>,
+}
+
+impl<I> SyntaxError<I> {
+    pub(crate) fn expected(input: I, what: &'static str) -> Self {
+        Self {
+            input,
+            expected: vec![Expected::Context(what)],
+            message: None,
+        }
+    }
+
+    pub(crate) fn message(input: I, message: String) -> Self {
+        Self {
+            input,
+            expected: Vec::new(),
+            message: Some(message),
+        }
+    }
+}
+
+impl<I: InputLength> ParseError<I> for SyntaxError<I> {
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        Self {
+            input,
+            expected: vec![Expected::Kind(kind)],
+            message: None,
+        }
+    }
+
+    fn append(_input: I, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: I, c: char) -> Self {
+        Self {
+            input,
+            expected: vec![Expected::Char(c)],
+            message: None,
+        }
+    }
+
+    fn or(self, other: Self) -> Self {
+        match self.input.input_len().cmp(&other.input.input_len()) {
+            std::cmp::Ordering::Less => self,
+            std::cmp::Ordering::Greater => other,
+            std::cmp::Ordering::Equal => {
+                let mut merged = self;
+                for e in other.expected {
+                    if !merged.expected.contains(&e) {
+                        merged.expected.push(e);
+                    }
+                }
+                merged
+            }
+        }
+    }
+}
+
+impl<I: InputLength> ContextError<I> for SyntaxError<I> {
+    fn add_context(input: I, ctx: &'static str, other: Self) -> Self {
+        if input.input_len() == other.input.input_len() {
+            Self::expected(input, ctx)
+        } else {
+            other
+        }
+    }
+}
+
+impl<I: InputLength, E> FromExternalError<I, E> for SyntaxError<I> {
+    fn from_external_error(input: I, kind: ErrorKind, _e: E) -> Self {
+        Self::from_error_kind(input, kind)
+    }
+}
+
+impl Expected {
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            Expected::Char(c) => format!("`{c}`"),
+            Expected::Context(ctx) => ctx.to_string(),
+            Expected::Kind(kind) => match kind {
+                ErrorKind::Tag => "keyword".to_string(),
+                ErrorKind::Digit => "digits".to_string(),
+                ErrorKind::HexDigit => "hexadecimal digits".to_string(),
+                ErrorKind::OctDigit => "octal digits".to_string(),
+                ErrorKind::IsA => "binary digits".to_string(),
+                ErrorKind::MultiSpace => "whitespace".to_string(),
+                ErrorKind::TakeUntil => "closing delimiter".to_string(),
+                ErrorKind::Eof => "end of file".to_string(),
+                kind => kind.description().to_lowercase(),
+            },
+        }
+    }
+}
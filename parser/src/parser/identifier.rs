@@ -1,5 +1,10 @@
-use nom::bytes::complete::take_while;
-use nom::IResult;
+use nom::bytes::complete::{tag, take_while};
+use nom::character::complete::{char, satisfy};
+use nom::combinator::{not, opt, recognize};
+use nom::error::context;
+use nom::sequence::{pair, preceded, terminated};
+
+use crate::parser::error::IResult;
 
 fn is_identifier_prefix(c: char) -> bool {
     c.is_alphabetic() || c == '_'
@@ -10,8 +15,44 @@ fn is_identifier_body(c: char) -> bool {
 }
 
 pub(super) fn identifier(s: &str) -> IResult<&str, String> {
-    let (s, prefix) = take_while(is_identifier_prefix)(s)?;
-    let (s, body) = take_while(is_identifier_body)(s)?;
+    let (s, ident) = context(
+        "identifier",
+        recognize(pair(
+            satisfy(is_identifier_prefix),
+            take_while(is_identifier_body),
+        )),
+    )(s)?;
+
+    Ok((s, ident.to_string()))
+}
+
+/// Name referring to a definition, optionally qualified by the namespace of an import as in
+/// `errors.NotFound`.
+pub(super) fn qualified_identifier(s: &str) -> IResult<&str, String> {
+    let (s, name) = context(
+        "identifier",
+        recognize(pair(identifier, opt(preceded(char('.'), identifier)))),
+    )(s)?;
+
+    Ok((s, name.to_string()))
+}
+
+/// Name of an attribute or an object literal key, which may also contain `-` as in
+/// `x-codegen`.
+pub(super) fn attribute_name(s: &str) -> IResult<&str, String> {
+    let (s, name) = context(
+        "identifier",
+        recognize(pair(
+            satisfy(is_identifier_prefix),
+            take_while(|c| is_identifier_body(c) || c == '-'),
+        )),
+    )(s)?;
+
+    Ok((s, name.to_string()))
+}
 
-    Ok((s, prefix.to_string() + body))
+/// Matches `word` only as a whole identifier, so that `Float` does not match the head of
+/// `Float32` or `FloatRange`.
+pub(super) fn keyword(word: &'static str) -> impl Fn(&str) -> IResult<&str, &str> {
+    move |s| terminated(tag(word), not(satisfy(is_identifier_body)))(s)
 }
@@ -1,19 +1,65 @@
+use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::streaming::multispace1;
-use nom::IResult;
+use nom::character::complete::char;
+use nom::combinator::{cut, opt};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, preceded, terminated};
 
-use crate::data::Import;
+use crate::data::{Import, ImportItems};
+use crate::parser::error::IResult;
+use crate::parser::identifier::{identifier, keyword};
 use crate::parser::literals::string_literal;
+use crate::parser::whitespace::{multispace0, multispace1};
 
 pub(super) fn import_statement(s: &str) -> IResult<&str, Import> {
     let (s, _) = tag("import")(s)?;
+    let (s, import) = cut(alt((selective_import, file_import)))(s)?;
+    let (s, _) = opt(preceded(multispace0, char(';')))(s)?;
+
+    Ok((s, import))
+}
+
+/// `import "file"` or `import "file" as ns`
+fn file_import(s: &str) -> IResult<&str, Import> {
     let (s, _) = multispace1(s)?;
     let (s, file) = string_literal(s)?;
+    let (s, namespace) = opt(preceded(
+        delimited(multispace1, keyword("as"), multispace1),
+        cut(identifier),
+    ))(s)?;
+
+    Ok((
+        s,
+        Import {
+            file: file.to_string(),
+            items: match namespace {
+                None => ImportItems::All,
+                Some(ns) => ImportItems::Namespace(ns),
+            },
+        },
+    ))
+}
+
+/// `import { A, B } from "file"`
+fn selective_import(s: &str) -> IResult<&str, Import> {
+    let (s, _) = multispace0(s)?;
+    let (s, _) = char('{')(s)?;
+    let (s, names) = cut(delimited(
+        multispace0,
+        terminated(
+            separated_list1(delimited(multispace0, char(','), multispace0), identifier),
+            opt(preceded(multispace0, char(','))),
+        ),
+        preceded(multispace0, char('}')),
+    ))(s)?;
+    let (s, _) = cut(delimited(multispace0, keyword("from"), multispace1))(s)?;
+    let (s, file) = cut(string_literal)(s)?;
 
     Ok((
         s,
         Import {
             file: file.to_string(),
+            items: ImportItems::Selected(names),
         },
     ))
 }
@@ -1,20 +1,22 @@
-use crate::parser::definition_head;
-use crate::parser::identifier::identifier;
+use crate::parser::error::{IResult, SyntaxError};
+use crate::parser::identifier::{identifier, keyword, qualified_identifier};
 use crate::parser::literals::string_literal;
+use crate::parser::whitespace::{multispace0, multispace1};
+use crate::parser::{definition_head, wrapper};
 use crate::Info;
-use nom::branch::{alt, permutation};
+use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::{char, multispace0, multispace1};
-use nom::combinator::opt;
-use nom::sequence::{delimited, preceded};
-use nom::IResult;
+use nom::character::complete::char;
+use nom::combinator::{cut, opt};
+use nom::multi::{many0, separated_list0};
+use nom::sequence::{delimited, preceded, terminated};
 
 pub(super) fn info_definition(s: &str) -> IResult<&str, Info> {
     let (s, default_spec) = opt(default_spec)(s)?;
     let (s, name) = definition_head("info")(s)?;
     let (s, base_info) = opt(extends)(s)?;
     let (s, _) = multispace0(s)?;
-    delimited(
+    cut(delimited(
         char('{'),
         delimited(
             multispace0,
@@ -22,7 +24,7 @@ pub(super) fn info_definition(s: &str) -> IResult<&str, Info> {
             multispace0,
         ),
         char('}'),
-    )(s)
+    ))(s)
 }
 
 fn extends(s: &str) -> IResult<&str, String> {
@@ -47,36 +49,71 @@ fn extends_reserved_word(s: &str) -> IResult<&str, String> {
     Ok((s, base))
 }
 
+enum InfoEntry {
+    Title(String),
+    Summary(String),
+    Description(String),
+    Version(String),
+    Servers(Vec<String>),
+}
+
 fn info_content(
     is_default: bool,
     config_name: String,
     base: Option<String>,
 ) -> impl Fn(&str) -> IResult<&str, Info> {
     move |s: &str| {
-        let (s, (title, summary, description, version)) = permutation((
-            assignment_option("title"),
-            assignment_option("summary"),
-            assignment_option("description"),
-            assignment_option("version"),
-        ))(s)?;
-        Ok((
-            s,
-            Info {
-                is_default,
-                config_name: config_name.to_string(),
-                title,
-                summary,
-                description,
-                terms_of_service: None,
-                version,
-                base: base.clone(),
-            },
-        ))
+        let (s, entries) = many0(|s| {
+            let (rest, entry) = alt((
+                assignment("title", InfoEntry::Title),
+                assignment("summary", InfoEntry::Summary),
+                assignment("description", InfoEntry::Description),
+                assignment("version", InfoEntry::Version),
+                wrapper(servers_assignment, InfoEntry::Servers),
+            ))(s)?;
+            Ok((rest, (s, entry)))
+        })(s)?;
+
+        let mut info = Info {
+            is_default,
+            config_name: config_name.to_string(),
+            base: base.clone(),
+            ..Info::default()
+        };
+        for (at, entry) in entries {
+            let set = match entry {
+                InfoEntry::Title(v) => info.title.replace(v).is_none(),
+                InfoEntry::Summary(v) => info.summary.replace(v).is_none(),
+                InfoEntry::Description(v) => info.description.replace(v).is_none(),
+                InfoEntry::Version(v) => info.version.replace(v).is_none(),
+                InfoEntry::Servers(v) => info.servers.replace(v).is_none(),
+            };
+            if !set {
+                let key = at
+                    .split(|c: char| !c.is_ascii_alphabetic())
+                    .next()
+                    .unwrap_or_default();
+                return Err(nom::Err::Failure(SyntaxError::message(
+                    at,
+                    format!("`{key}` is set more than once in info `{config_name}`"),
+                )));
+            }
+        }
+
+        Ok((s, info))
     }
 }
 
-fn assignment_option(name: &'static str) -> impl Fn(&str) -> IResult<&str, Option<String>> {
-    move |s: &str| opt(preceded(tag(name), tag_with_assignment))(s)
+fn assignment(
+    name: &'static str,
+    entry: fn(String) -> InfoEntry,
+) -> impl Fn(&str) -> IResult<&str, InfoEntry> {
+    move |s: &str| {
+        let (s, _) = keyword(name)(s)?;
+        let (s, value) = tag_with_assignment(s)?;
+
+        Ok((s, entry(value)))
+    }
 }
 
 fn tag_with_assignment(s: &str) -> IResult<&str, String> {
@@ -87,7 +124,27 @@ fn tag_with_assignment(s: &str) -> IResult<&str, String> {
     Ok((s, value.to_string()))
 }
 
-fn default_spec(s: &str) -> IResult<&str, ()> {
+/// `servers = [Production, Staging]`
+fn servers_assignment(s: &str) -> IResult<&str, Vec<String>> {
+    let (s, _) = keyword("servers")(s)?;
+    let (s, _) = cut(delimited(multispace0, char('='), multispace0))(s)?;
+    let (s, servers) = cut(delimited(
+        terminated(char('['), multispace0),
+        terminated(
+            separated_list0(
+                delimited(multispace0, char(','), multispace0),
+                qualified_identifier,
+            ),
+            opt(preceded(multispace0, char(','))),
+        ),
+        preceded(multispace0, char(']')),
+    ))(s)?;
+    let (s, _) = multispace0(s)?;
+
+    Ok((s, servers))
+}
+
+pub(super) fn default_spec(s: &str) -> IResult<&str, ()> {
     let (s, _) = tag("default")(s)?;
     let (s, _) = multispace1(s)?;
 
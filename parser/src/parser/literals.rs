@@ -1,23 +1,30 @@
 use crate::data::Literal;
+use crate::parser::error::{IResult, SyntaxError};
+use crate::parser::identifier::keyword;
 use crate::parser::wrapper;
 use nom::branch::alt;
 use nom::bytes::complete::{is_a, tag, take_until};
-use nom::character::complete::{char, digit1, hex_digit1, oct_digit1};
-use nom::error::{ErrorKind, ParseError};
-use nom::sequence::delimited;
-use nom::IResult;
+use nom::character::complete::{char, digit0, digit1, hex_digit1, oct_digit1, one_of};
+use nom::combinator::{opt, recognize};
+use nom::error::{context, ErrorKind, ParseError};
+use nom::sequence::{delimited, pair, tuple};
 
 pub(super) fn literal(s: &str) -> IResult<&str, Literal> {
     alt((
         wrapper(string_literal, |v| Literal::String(v.to_string())),
+        wrapper(float_literal, Literal::Float),
         wrapper(integer_literal, Literal::Int),
         wrapper(bool_literal, Literal::Bool),
+        wrapper(null_literal, |_| Literal::Null),
     ))(s)
 }
 
 // string
 pub(super) fn string_literal(s: &str) -> IResult<&str, &str> {
-    alt((simple_string_literal, raw_string_literal))(s)
+    context(
+        "string literal",
+        alt((simple_string_literal, raw_string_literal)),
+    )(s)
 }
 
 fn simple_string_literal(s: &str) -> IResult<&str, &str> {
@@ -30,18 +37,22 @@ fn raw_string_literal(s: &str) -> IResult<&str, &str> {
 
 // integer
 pub(super) fn integer_literal(s: &str) -> IResult<&str, i64> {
-    alt((
+    let (s, negative) = opt(char('-'))(s)?;
+    let (s, n) = alt((
         binary_integer_literal,
         octal_integer_literal,
         hexadecimal_integer_literal,
         decimal_integer_literal,
-    ))(s)
+    ))(s)?;
+
+    Ok((s, if negative.is_some() { -n } else { n }))
 }
 
 fn decimal_integer_literal(s: &str) -> IResult<&str, i64> {
     let (s, n) = digit1(s)?;
-    let n = i64::from_str_radix(n, 10)
-        .map_err(|_| nom::Err::Error(nom::error::Error::from_error_kind(s, ErrorKind::Digit)))?;
+    let n = n
+        .parse::<i64>()
+        .map_err(|_| nom::Err::Error(SyntaxError::from_error_kind(s, ErrorKind::Digit)))?;
 
     Ok((s, n))
 }
@@ -50,7 +61,7 @@ fn hexadecimal_integer_literal(s: &str) -> IResult<&str, i64> {
     let (s, _) = tag("0x")(s)?;
     let (s, n) = hex_digit1(s)?;
     let n = i64::from_str_radix(n, 16)
-        .map_err(|_| nom::Err::Error(nom::error::Error::from_error_kind(s, ErrorKind::Digit)))?;
+        .map_err(|_| nom::Err::Error(SyntaxError::from_error_kind(s, ErrorKind::Digit)))?;
 
     Ok((s, n))
 }
@@ -59,7 +70,7 @@ fn octal_integer_literal(s: &str) -> IResult<&str, i64> {
     let (s, _) = tag("0o")(s)?;
     let (s, n) = oct_digit1(s)?;
     let n = i64::from_str_radix(n, 8)
-        .map_err(|_| nom::Err::Error(nom::error::Error::from_error_kind(s, ErrorKind::Digit)))?;
+        .map_err(|_| nom::Err::Error(SyntaxError::from_error_kind(s, ErrorKind::Digit)))?;
 
     Ok((s, n))
 }
@@ -68,13 +79,89 @@ fn binary_integer_literal(s: &str) -> IResult<&str, i64> {
     let (s, _) = tag("0b")(s)?;
     let (s, n) = is_a("01")(s)?;
     let n = i64::from_str_radix(n, 2)
-        .map_err(|_| nom::Err::Error(nom::error::Error::from_error_kind(s, ErrorKind::Digit)))?;
+        .map_err(|_| nom::Err::Error(SyntaxError::from_error_kind(s, ErrorKind::Digit)))?;
 
     Ok((s, n))
 }
 
+// float
+/// `1.5`, `.5`, `1.`, `-0.25` or `1e-3`; a number without a fraction or an exponent is an
+/// integer.
+fn float_literal(s: &str) -> IResult<&str, f64> {
+    let exponent = || tuple((one_of("eE"), opt(one_of("+-")), digit1));
+    let (rest, n) = recognize(pair(
+        opt(char('-')),
+        alt((
+            recognize(tuple((digit1, char('.'), digit0, opt(exponent())))),
+            recognize(tuple((char('.'), digit1, opt(exponent())))),
+            recognize(pair(digit1, exponent())),
+        )),
+    ))(s)?;
+    let n = n
+        .parse::<f64>()
+        .map_err(|_| nom::Err::Error(SyntaxError::from_error_kind(s, ErrorKind::Float)))?;
+
+    Ok((rest, n))
+}
+
 // bool
 pub(super) fn bool_literal(s: &str) -> IResult<&str, bool> {
-    let (s, v) = alt((tag("true"), tag("false")))(s)?;
+    let (s, v) = alt((keyword("true"), keyword("false")))(s)?;
     Ok((s, v == "true"))
 }
+
+// null
+fn null_literal(s: &str) -> IResult<&str, &str> {
+    keyword("null")(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Literal {
+        let (rest, literal) = literal(s).unwrap();
+        assert_eq!(rest, "", "`{s}` was not parsed to the end");
+        literal
+    }
+
+    #[test]
+    fn integers() {
+        assert!(matches!(parse("42"), Literal::Int(42)));
+        assert!(matches!(parse("-42"), Literal::Int(-42)));
+        assert!(matches!(parse("0x1f"), Literal::Int(31)));
+        assert!(matches!(parse("0o17"), Literal::Int(15)));
+        assert!(matches!(parse("0b101"), Literal::Int(5)));
+    }
+
+    #[test]
+    fn floats() {
+        for (s, expected) in [
+            ("1.5", 1.5),
+            (".5", 0.5),
+            ("1.", 1.0),
+            ("-0.25", -0.25),
+            ("1e-3", 0.001),
+            ("2.5E2", 250.0),
+        ] {
+            match parse(s) {
+                Literal::Float(n) => assert_eq!(n, expected, "{s}"),
+                other => panic!("`{s}` parsed as {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn strings() {
+        assert!(matches!(parse(r#""text""#), Literal::String(s) if s == "text"));
+        assert!(matches!(parse(r##"r#"say "hi""#"##), Literal::String(s) if s == r#"say "hi""#));
+    }
+
+    #[test]
+    fn keywords() {
+        assert!(matches!(parse("true"), Literal::Bool(true)));
+        assert!(matches!(parse("false"), Literal::Bool(false)));
+        assert!(matches!(parse("null"), Literal::Null));
+        assert!(literal("nullable").is_err());
+    }
+}
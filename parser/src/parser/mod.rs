@@ -1,15 +1,23 @@
 use nom::bytes::complete::tag;
-use nom::character::complete::{multispace0, multispace1};
-use nom::combinator::{all_consuming, opt};
-use nom::multi::separated_list0;
-use nom::IResult;
+use nom::character::complete::one_of;
+use nom::combinator::opt;
+use nom::error::{context, ErrorKind, ParseError as _};
+use nom::multi::many0;
+use nom::sequence::{delimited, preceded};
 
-use crate::data::SourceFileContent;
+use std::cell::RefCell;
+
+use crate::data::{LineIndex, SourceFileContent, Span, Spanned};
+use crate::error::ParseError;
+use crate::parser::error::{IResult, SyntaxError};
 use crate::parser::identifier::identifier;
 use crate::parser::import_statement::import_statement;
-use crate::parser::object_definition::object_definition;
+use crate::parser::object_definition::{documented_object, object_definition};
+use crate::parser::whitespace::{doc_comment, multispace0, multispace1};
 
+mod constant_definition;
 mod enum_definition;
+mod error;
 mod identifier;
 mod import_statement;
 mod info_definition;
@@ -20,28 +28,140 @@ mod path_definition;
 mod request_body_definition;
 mod response_definition;
 mod schema_definition;
+mod security_definition;
+mod server_definition;
 mod tag_definition;
 mod value;
+mod whitespace;
 mod with_attributes;
 
-pub fn parse(input: &str) -> Result<SourceFileContent, nom::Err<nom::error::Error<&str>>> {
-    let (_, s) = parse_impl(input)?;
-    Ok(s)
+thread_local! {
+    /// Source file being parsed, for the parsers to record where they matched.
+    static SOURCE: RefCell<Option<LineIndex>> = const { RefCell::new(None) };
+}
+
+/// Parses the content of the source file `file`.
+pub fn parse(file: &str, input: &str) -> Result<SourceFileContent, ParseError> {
+    with_source(file, input, || match source_file_content(input) {
+        Ok((_, s)) => Ok(s),
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Err(parse_error(e)),
+        Err(nom::Err::Incomplete(_)) => Err(parse_error(SyntaxError::from_error_kind(
+            "",
+            ErrorKind::Eof,
+        ))),
+    })
+}
+
+/// Runs `f`, which parses `input`, with spans recorded in the file `file`.
+fn with_source<T>(file: &str, input: &str, f: impl FnOnce() -> T) -> T {
+    let outer = SOURCE.replace(Some(LineIndex::new(file, input)));
+    let result = f();
+    SOURCE.set(outer);
+    result
+}
+
+/// Span of the input parsed between the rests `before` and `after` of the source file.
+pub(crate) fn span(before: &str, after: &str) -> Span {
+    SOURCE.with_borrow(|source| {
+        source
+            .as_ref()
+            .expect("spans are recorded while parsing a source file")
+            .span_between(before, after)
+    })
+}
+
+/// Runs the parser `f` on `input` as the whole content of a source file.
+#[cfg(test)]
+pub(crate) fn parse_source<'a, O>(
+    f: impl Fn(&'a str) -> IResult<&'a str, O>,
+    input: &'a str,
+) -> IResult<&'a str, O> {
+    with_source("test.oai", input, || f(input))
+}
+
+fn parse_error(e: SyntaxError<&str>) -> ParseError {
+    let found = found_token(e.input);
+
+    // low level error kinds only add noise when something more specific is expected
+    let specific = e
+        .expected
+        .iter()
+        .filter(|x| !matches!(x, error::Expected::Kind(_)))
+        .collect::<Vec<_>>();
+    let expected = if specific.is_empty() {
+        e.expected.iter().collect()
+    } else {
+        specific
+    };
+
+    ParseError {
+        span: Box::new(span(e.input, &e.input[found.len()..])),
+        expected: expected.into_iter().map(|x| x.describe()).collect(),
+        found: if found.is_empty() {
+            "end of file".to_string()
+        } else {
+            format!("`{found}`")
+        },
+        message: e.message,
+    }
 }
 
-fn parse_impl(s: &str) -> IResult<&str, SourceFileContent> {
-    all_consuming(source_file_content)(s)
+fn found_token(s: &str) -> &str {
+    match s.chars().next() {
+        None => "",
+        Some(c) if c.is_alphanumeric() || c == '_' => {
+            let end = s
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(s.len());
+            &s[..end]
+        }
+        Some(c) => &s[..c.len_utf8()],
+    }
 }
 
 fn source_file_content(s: &str) -> IResult<&str, SourceFileContent> {
-    let (s, imports) = separated_list0(multispace1, import_statement)(s)?;
-    let (s, _) = multispace0(s)?;
-    let (s, objects) = separated_list0(multispace1, object_definition)(s)?;
+    let (s, imports) = many0(preceded(multispace0, spanned(import_statement)))(s)?;
+    let (s, objects) = many0(|s| {
+        let (s, doc) = doc_comment(s)?;
+        let (s, object) = spanned(object_definition)(s)?;
+        Ok((
+            s,
+            Spanned {
+                span: object.span,
+                value: documented_object(object.value, doc),
+            },
+        ))
+    })(s)?;
     let (s, _) = multispace0(s)?;
 
+    if !s.is_empty() {
+        // run the object parser once more to find out why it stopped here
+        let (s, _) = context("object definition", object_definition)(s)?;
+        return Err(nom::Err::Error(SyntaxError::from_error_kind(
+            s,
+            ErrorKind::MultiSpace,
+        )));
+    }
+
     Ok((s, SourceFileContent { imports, objects }))
 }
 
+fn spanned<'a, O, F>(f: F) -> impl Fn(&'a str) -> IResult<&'a str, Spanned<O>>
+where
+    F: Fn(&'a str) -> IResult<&'a str, O>,
+{
+    move |s| {
+        let (rest, value) = f(s)?;
+        Ok((
+            rest,
+            Spanned {
+                span: span(s, rest),
+                value,
+            },
+        ))
+    }
+}
+
 fn wrapper<'a, O, F, Tr, O2>(f: F, t: Tr) -> impl Fn(&'a str) -> IResult<&'a str, O>
 where
     F: Fn(&'a str) -> IResult<&'a str, O2>,
@@ -86,6 +206,19 @@ fn shorthand_definition_head(type_name: &'static str) -> impl Fn(&str) -> IResul
     }
 }
 
+/// An entry of a `{ ... }` block, followed by an optional `;` or `,`.
+fn block_entry<'a, O>(
+    parser: impl Fn(&'a str) -> IResult<&'a str, O>,
+) -> impl Fn(&'a str) -> IResult<&'a str, O> {
+    move |s| {
+        delimited(
+            multispace0,
+            &parser,
+            opt(preceded(multispace0, one_of(";,"))),
+        )(s)
+    }
+}
+
 fn opt_permutation<P1, P2, O1, O2>(
     parsers: (P1, P2),
 ) -> impl Fn(&str) -> IResult<&str, (Option<O1>, Option<O2>)>
@@ -1,13 +1,16 @@
 use nom::branch::alt;
-use nom::IResult;
 
-use crate::data::Object;
+use crate::data::{Enum, Object, Schema, SecurityScheme, Server};
+use crate::parser::constant_definition::constant_definition;
 use crate::parser::enum_definition::enum_definition;
+use crate::parser::error::IResult;
 use crate::parser::info_definition::info_definition;
 use crate::parser::path_definition::path_definition;
 use crate::parser::request_body_definition::request_body_definition;
 use crate::parser::response_definition::response_definition;
 use crate::parser::schema_definition::schema_definition;
+use crate::parser::security_definition::security_definition;
+use crate::parser::server_definition::server_definition;
 use crate::parser::tag_definition::tag_definition;
 use crate::parser::wrapper;
 
@@ -17,8 +20,24 @@ pub(super) fn object_definition(s: &str) -> IResult<&str, Object> {
         wrapper(enum_definition, Object::Enum),
         wrapper(tag_definition, Object::Tag),
         wrapper(response_definition, Object::Response),
-        wrapper(path_definition, Object::Path),
+        wrapper(path_definition, |p| Object::Path(Box::new(p))),
         wrapper(request_body_definition, Object::RequestBody),
         wrapper(info_definition, Object::Info),
+        wrapper(constant_definition, Object::Constant),
+        wrapper(server_definition, Object::Server),
+        wrapper(security_definition, Object::SecurityScheme),
     ))(s)
 }
+
+pub(super) fn documented_object(object: Object, doc: Option<String>) -> Object {
+    match object {
+        Object::Schema(schema) => Object::Schema(Schema { doc, ..schema }),
+        Object::Enum(en) => Object::Enum(Enum { doc, ..en }),
+        Object::SecurityScheme(scheme) => Object::SecurityScheme(SecurityScheme { doc, ..scheme }),
+        Object::Server(server) => Object::Server(Server {
+            description: server.description.or(doc),
+            ..server
+        }),
+        object => object,
+    }
+}
@@ -1,10 +1,12 @@
 use nom::bytes::complete::tag;
-use nom::character::complete::{char, multispace0};
+use nom::character::complete::char;
 use nom::combinator::opt;
 use nom::sequence::delimited;
-use nom::IResult;
 
+use crate::parser::error::IResult;
 use crate::parser::schema_definition::{data_type, requirement_spec};
+use crate::parser::span;
+use crate::parser::whitespace::multispace0;
 use crate::parser::with_attributes::with_attributes;
 use crate::{Parameter, ParameterContent, ParameterType, Requirement, TypeWithAttributes};
 
@@ -15,17 +17,20 @@ mod shorthand;
 pub(super) use parameters::*;
 pub(super) use shorthand::*;
 
-fn parameter_definition_inner(
+fn parameter_definition_inner<'a>(
     name: String,
     ty: Option<ParameterType>,
-) -> impl Fn(&str) -> IResult<&str, Parameter> {
-    move |s: &str| {
+    start: &'a str,
+) -> impl Fn(&'a str) -> IResult<&'a str, Parameter> {
+    move |s: &'a str| {
         let (s, content) = parameter_content(s)?;
 
         Ok((
             s,
             Parameter {
                 name: name.clone(),
+                span: span(start, s),
+                doc: None,
                 content,
                 ty: ty.clone(),
             },
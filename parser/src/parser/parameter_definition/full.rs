@@ -1,44 +1,44 @@
 use crate::parser::definition_head;
+use crate::parser::error::IResult;
 use crate::parser::parameter_definition::parameter_definition_inner;
 use crate::{Parameter, ParameterType};
-use nom::IResult;
 
 #[allow(dead_code)]
 pub(in crate::parser) fn parameter_definition_with_type(
     ty: ParameterType,
 ) -> impl Fn(&str) -> IResult<&str, Parameter> {
-    move |s: &str| {
-        let (s, name) = definition_head("parameter")(s)?;
-        parameter_definition_inner(name, Some(ty.clone()))(s)
+    move |start: &str| {
+        let (s, name) = definition_head("parameter")(start)?;
+        parameter_definition_inner(name, Some(ty.clone()), start)(s)
     }
 }
 
 #[allow(dead_code)]
-pub(in crate::parser) fn parameter_definition(s: &str) -> IResult<&str, Parameter> {
-    let (s, name) = definition_head("parameter")(s)?;
-    parameter_definition_inner(name, None)(s)
+pub(in crate::parser) fn parameter_definition(start: &str) -> IResult<&str, Parameter> {
+    let (s, name) = definition_head("parameter")(start)?;
+    parameter_definition_inner(name, None, start)(s)
 }
 
 #[allow(dead_code)]
-pub(in crate::parser) fn query_definition(s: &str) -> IResult<&str, Parameter> {
-    let (s, name) = definition_head("query")(s)?;
-    parameter_definition_inner(name, Some(ParameterType::Query))(s)
+pub(in crate::parser) fn query_definition(start: &str) -> IResult<&str, Parameter> {
+    let (s, name) = definition_head("query")(start)?;
+    parameter_definition_inner(name, Some(ParameterType::Query), start)(s)
 }
 
 #[allow(dead_code)]
-pub(in crate::parser) fn path_parameter_definition(s: &str) -> IResult<&str, Parameter> {
-    let (s, name) = definition_head("pathParameter")(s)?;
-    parameter_definition_inner(name, Some(ParameterType::Path))(s)
+pub(in crate::parser) fn path_parameter_definition(start: &str) -> IResult<&str, Parameter> {
+    let (s, name) = definition_head("pathParameter")(start)?;
+    parameter_definition_inner(name, Some(ParameterType::Path), start)(s)
 }
 
 #[allow(dead_code)]
-pub(in crate::parser) fn header_definition(s: &str) -> IResult<&str, Parameter> {
-    let (s, name) = definition_head("header")(s)?;
-    parameter_definition_inner(name, Some(ParameterType::Header))(s)
+pub(in crate::parser) fn header_definition(start: &str) -> IResult<&str, Parameter> {
+    let (s, name) = definition_head("header")(start)?;
+    parameter_definition_inner(name, Some(ParameterType::Header), start)(s)
 }
 
 #[allow(dead_code)]
-pub(in crate::parser) fn cookie_definition(s: &str) -> IResult<&str, Parameter> {
-    let (s, name) = definition_head("cookie")(s)?;
-    parameter_definition_inner(name, Some(ParameterType::Cookie))(s)
+pub(in crate::parser) fn cookie_definition(start: &str) -> IResult<&str, Parameter> {
+    let (s, name) = definition_head("cookie")(start)?;
+    parameter_definition_inner(name, Some(ParameterType::Cookie), start)(s)
 }
@@ -1,14 +1,15 @@
+use crate::parser::error::IResult;
 use crate::parser::identifier::identifier;
 use crate::parser::literals::string_literal;
 use crate::parser::parameter_definition::inferred_shorthand_parameter_definition;
-use crate::parser::{shorthand_definition_head, wrapper_to_string};
+use crate::parser::whitespace::{doc_comment, multispace0};
+use crate::parser::{shorthand_definition_head, span, wrapper_to_string};
 use crate::{Parameter, ParameterType, Parameters};
 use nom::branch::alt;
-use nom::character::complete::{char, multispace0};
-use nom::combinator::opt;
+use nom::character::complete::char;
+use nom::combinator::{cut, opt};
 use nom::multi::separated_list0;
-use nom::sequence::delimited;
-use nom::IResult;
+use nom::sequence::{delimited, terminated};
 
 pub(in crate::parser) fn parameters_definition(
     context_type: ParameterType,
@@ -18,11 +19,7 @@ pub(in crate::parser) fn parameters_definition(
         let (s, _) = multispace0(s)?;
         delimited(
             char('{'),
-            delimited(
-                multispace0,
-                parameters_content(context_type.clone()),
-                multispace0,
-            ),
+            terminated(parameters_content(context_type.clone()), multispace0),
             char('}'),
         )(s)
     }
@@ -42,16 +39,21 @@ fn parameters_content_inner(
     context_type: ParameterType,
 ) -> impl Fn(&str) -> IResult<&str, Parameter> {
     move |s: &str| {
-        let (s, _) = multispace0(s)?;
-        let (s, name) = alt((wrapper_to_string(string_literal), identifier))(s)?;
+        let (start, doc) = doc_comment(s)?;
+        let (s, name) = alt((wrapper_to_string(string_literal), identifier))(start)?;
         let (s, _) = delimited(multispace0, char(':'), multispace0)(s)?;
-        let (s, content) = inferred_shorthand_parameter_definition(context_type.clone())(s)?;
+        let (s, content) = cut(inferred_shorthand_parameter_definition(
+            context_type.clone(),
+        ))(s)?;
+        let span = span(start, s);
         let (s, _) = multispace0(s)?;
 
         Ok((
             s,
             Parameter {
                 name,
+                span,
+                doc,
                 ty: Some(context_type.clone()),
                 content,
             },
@@ -1,8 +1,8 @@
+use crate::parser::error::IResult;
 use crate::parser::parameter_definition::parameter_content;
 use crate::parser::shorthand_definition_head;
 use crate::{ParameterContent, ParameterType};
 use nom::branch::alt;
-use nom::IResult;
 
 pub(in crate::parser) fn inferred_shorthand_parameter_definition(
     context_type: ParameterType,
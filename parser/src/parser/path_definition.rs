@@ -1,11 +1,12 @@
-use nom::character::complete::{char, multispace0};
-use nom::combinator::opt;
-use nom::sequence::delimited;
-use nom::IResult;
+use nom::character::complete::char;
+use nom::combinator::{cut, opt};
+use nom::sequence::{delimited, terminated};
 
 use crate::data::Path;
+use crate::parser::error::IResult;
 use crate::parser::literals::string_literal;
 use crate::parser::path_definition::operation::path_content;
+use crate::parser::whitespace::multispace0;
 use crate::parser::with_attributes::with_attributes;
 use crate::parser::{definition_head_with_parser, wrapper_to_string};
 
@@ -16,11 +17,11 @@ pub(super) fn path_definition(s: &str) -> IResult<&str, Path> {
     let (s, attributes) = opt(with_attributes)(s)?;
     let (s, _) = multispace0(s)?;
 
-    let (s, content) = delimited(
+    let (s, content) = cut(delimited(
         char('{'),
-        delimited(multispace0, path_content, multispace0),
+        terminated(|s| path_content(s, &name), multispace0),
         char('}'),
-    )(s)?;
+    ))(s)?;
 
     Ok((
         s,
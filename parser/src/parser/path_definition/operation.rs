@@ -1,57 +1,60 @@
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::{char, digit1, multispace0};
-use nom::character::streaming::multispace1;
-use nom::combinator::opt;
-use nom::error::ErrorKind;
-use nom::multi::{separated_list0, separated_list1};
-use nom::sequence::delimited;
-use nom::IResult;
+use nom::character::complete::{char, digit1};
+use nom::combinator::{cut, opt};
+use nom::error::{context, ErrorKind, ParseError};
+use nom::multi::{many1, separated_list0, separated_list1};
+use nom::sequence::{delimited, preceded};
 
 use crate::data::{HttpMethod, Operation, OperationContent, Parameters, PathContent};
-use crate::parser::identifier::identifier;
+use crate::parser::error::{IResult, SyntaxError};
+use crate::parser::identifier::{identifier, qualified_identifier};
 use crate::parser::parameter_definition::parameters_definition;
 use crate::parser::request_body_definition::shorthand_request_body_definition;
 use crate::parser::response_definition::shorthand_response_definition;
+use crate::parser::span;
+use crate::parser::whitespace::{doc_comment, multispace0, multispace1};
+use crate::parser::with_attributes::with_attributes;
 use crate::{ParameterType, ReferenceOr, RequestBodyContent, ResponseContent};
 
-pub(super) fn path_content(s: &str) -> IResult<&str, PathContent> {
-    let (s, parameters) = opt(path_operation_parameter)(s)?;
-    let (s, operations) = separated_list1(multispace1, operation)(s)?;
+pub(super) fn path_content<'a>(s: &'a str, path: &str) -> IResult<&'a str, PathContent> {
+    let (s, parameters) = opt(preceded(multispace0, path_operation_parameter))(s)?;
+    let (s, operations) = many1(|s| {
+        // remember where the method keyword starts to point duplicates at it
+        let (at, _) = doc_comment(s)?;
+        let (s, op) = operation(s)?;
+        Ok((s, (at, op)))
+    })(s)?;
 
     let mut contents = PathContent {
         parameters: parameters.unwrap_or_default(),
         ..Default::default()
     };
 
-    for op in operations {
-        match op.method {
-            HttpMethod::Default => {}
-            HttpMethod::Get => {
-                contents.get = Some(op);
-            }
-            HttpMethod::Post => {
-                contents.post = Some(op);
-            }
-            HttpMethod::Put => {
-                contents.put = Some(op);
-            }
-            HttpMethod::Delete => {
-                contents.delete = Some(op);
-            }
-            HttpMethod::Options => {
-                contents.options = Some(op);
-            }
-            HttpMethod::Head => {
-                contents.head = Some(op);
-            }
-            HttpMethod::Trace => {
-                contents.trace = Some(op);
-            }
-            HttpMethod::Patch => {
-                contents.patch = Some(op);
-            }
+    for (at, op) in operations {
+        let slot = match op.method {
+            HttpMethod::Get => &mut contents.get,
+            HttpMethod::Post => &mut contents.post,
+            HttpMethod::Put => &mut contents.put,
+            HttpMethod::Delete => &mut contents.delete,
+            HttpMethod::Options => &mut contents.options,
+            HttpMethod::Head => &mut contents.head,
+            HttpMethod::Trace => &mut contents.trace,
+            HttpMethod::Patch => &mut contents.patch,
+        };
+
+        if slot.is_some() {
+            let keyword = at
+                .split(|c: char| !c.is_ascii_alphabetic())
+                .next()
+                .unwrap_or_default();
+            return Err(nom::Err::Failure(SyntaxError::message(
+                at,
+                format!("duplicate `{keyword}` operation for path \"{path}\""),
+            )));
         }
+
+        *slot = Some(op);
     }
 
     Ok((s, contents))
@@ -59,7 +62,6 @@ pub(super) fn path_content(s: &str) -> IResult<&str, PathContent> {
 
 fn path_operation_parameter(s: &str) -> IResult<&str, Parameters> {
     let (s, ps) = separated_list1(multispace1, operation_parameters)(s)?;
-    let (s, _) = multispace0(s)?;
 
     let mut param = Parameters::default();
 
@@ -70,7 +72,10 @@ fn path_operation_parameter(s: &str) -> IResult<&str, Parameters> {
             ParameterWithType::PathParameters(p) => param.parameters.extend(p.parameters),
             ParameterWithType::Cookies(p) => param.parameters.extend(p.parameters),
             ParameterWithType::RequestBody(_) => {
-                return Err(nom::Err::Error(nom::error::Error::new(s, ErrorKind::Alt)));
+                return Err(nom::Err::Error(SyntaxError::from_error_kind(
+                    s,
+                    ErrorKind::Alt,
+                )));
             }
         }
     }
@@ -79,40 +84,58 @@ fn path_operation_parameter(s: &str) -> IResult<&str, Parameters> {
 }
 
 fn operation(s: &str) -> IResult<&str, Operation> {
-    let (s, method) = method(s)?;
+    let (start, doc) = doc_comment(s)?;
+    let (s, method) = method(start)?;
     let (s, _) = multispace1(s)?;
-    let (s, name) = identifier(s)?;
-    let (s, (parameters, request_body)) = arguments(s)?;
-    let (s, _) = multispace0(s)?;
-    let (s, content) = delimited(
-        char('{'),
-        delimited(multispace0, content, multispace0),
-        char('}'),
-    )(s)?;
+    cut(operation_impl(method, doc, start))(s)
+}
 
-    Ok((
-        s,
-        Operation {
-            name,
-            method,
-            parameters,
-            content,
-            request_body,
-        },
-    ))
+fn operation_impl<'a>(
+    method: HttpMethod,
+    doc: Option<String>,
+    start: &'a str,
+) -> impl Fn(&'a str) -> IResult<&'a str, Operation> {
+    move |s: &'a str| {
+        let (s, name) = identifier(s)?;
+        let (s, (parameters, request_body)) = arguments(s)?;
+        let (s, attributes) = opt(with_attributes)(s)?;
+        let (s, _) = multispace0(s)?;
+        let (s, content) = delimited(
+            char('{'),
+            delimited(multispace0, |s| content(s, &name), multispace0),
+            char('}'),
+        )(s)?;
+
+        Ok((
+            s,
+            Operation {
+                name,
+                span: span(start, s),
+                doc: doc.clone(),
+                method: method.clone(),
+                parameters,
+                content,
+                request_body,
+                attributes: attributes.clone().unwrap_or_default(),
+            },
+        ))
+    }
 }
 
 fn method(s: &str) -> IResult<&str, HttpMethod> {
-    let (s, ms) = alt((
-        tag("get"),
-        tag("post"),
-        tag("put"),
-        tag("delete"),
-        tag("options"),
-        tag("head"),
-        tag("trace"),
-        tag("default"),
-    ))(s)?;
+    let (s, ms) = context(
+        "HTTP method",
+        alt((
+            tag("get"),
+            tag("post"),
+            tag("put"),
+            tag("delete"),
+            tag("options"),
+            tag("head"),
+            tag("trace"),
+            tag("patch"),
+        )),
+    )(s)?;
     let method = match ms {
         "get" => HttpMethod::Get,
         "post" => HttpMethod::Post,
@@ -121,26 +144,28 @@ fn method(s: &str) -> IResult<&str, HttpMethod> {
         "options" => HttpMethod::Options,
         "head" => HttpMethod::Head,
         "trace" => HttpMethod::Trace,
-        _ => HttpMethod::Default,
+        _ => HttpMethod::Patch,
     };
 
     Ok((s, method))
 }
 
 fn arguments(s: &str) -> IResult<&str, (Parameters, Option<ReferenceOr<RequestBodyContent>>)> {
-    delimited(
+    preceded(
         multispace0,
         delimited(
             char('('),
             delimited(multispace0, arguments_impl, multispace0),
             char(')'),
         ),
-        multispace0,
     )(s)
 }
 
 fn arguments_impl(s: &str) -> IResult<&str, (Parameters, Option<ReferenceOr<RequestBodyContent>>)> {
-    let (s, params) = separated_list0(char(','), operation_parameters)(s)?;
+    let (s, params) = separated_list0(
+        delimited(multispace0, char(','), multispace0),
+        operation_parameters,
+    )(s)?;
     let (s, _) = opt(char(','))(s)?;
 
     let mut parameters = Parameters::default();
@@ -152,7 +177,7 @@ fn arguments_impl(s: &str) -> IResult<&str, (Parameters, Option<ReferenceOr<Requ
             ParameterWithType::PathParameters(p) => parameters.parameters.extend(p.parameters),
             ParameterWithType::Cookies(c) => parameters.parameters.extend(c.parameters),
             ParameterWithType::RequestBody(r) => {
-                request_body = Some(r);
+                request_body = Some(*r);
             }
         }
     }
@@ -165,7 +190,7 @@ enum ParameterWithType {
     Headers(Parameters),
     PathParameters(Parameters),
     Cookies(Parameters),
-    RequestBody(ReferenceOr<RequestBodyContent>),
+    RequestBody(Box<ReferenceOr<RequestBodyContent>>),
 }
 
 fn operation_parameters(s: &str) -> IResult<&str, ParameterWithType> {
@@ -184,9 +209,14 @@ fn operation_parameters(s: &str) -> IResult<&str, ParameterWithType> {
                 "headers" => ParameterType::Header,
                 "pathParameters" => ParameterType::Path,
                 "cookie" => ParameterType::Cookie,
-                _ => return Err(nom::Err::Error(nom::error::Error::new(s, ErrorKind::Tag))),
+                _ => {
+                    return Err(nom::Err::Error(SyntaxError::from_error_kind(
+                        s,
+                        ErrorKind::Tag,
+                    )))
+                }
             };
-            let (s, parameters) = parameters_definition(ty.clone())(s)?;
+            let (s, parameters) = cut(parameters_definition(ty.clone()))(s)?;
 
             let params = match ty {
                 ParameterType::Query => ParameterWithType::Queries(parameters),
@@ -198,11 +228,14 @@ fn operation_parameters(s: &str) -> IResult<&str, ParameterWithType> {
         }
         "body" => {
             let (s, _) = delimited(multispace0, char('='), multispace0)(s)?;
-            let (s, content) = operation_request_body(s)?;
+            let (s, content) = cut(operation_request_body)(s)?;
 
-            Ok((s, ParameterWithType::RequestBody(content)))
+            Ok((s, ParameterWithType::RequestBody(Box::new(content))))
         }
-        _ => Err(nom::Err::Error(nom::error::Error::new(s, ErrorKind::Tag))),
+        _ => Err(nom::Err::Error(SyntaxError::from_error_kind(
+            s,
+            ErrorKind::Tag,
+        ))),
     }
 }
 
@@ -211,7 +244,7 @@ fn operation_request_body(s: &str) -> IResult<&str, ReferenceOr<RequestBodyConte
 }
 
 fn operation_request_body_ref(s: &str) -> IResult<&str, ReferenceOr<RequestBodyContent>> {
-    let (s, ident) = identifier(s)?;
+    let (s, ident) = qualified_identifier(s)?;
     Ok((s, ReferenceOr::Ref(ident.to_string())))
 }
 
@@ -220,17 +253,28 @@ fn operation_request_body_sh(s: &str) -> IResult<&str, ReferenceOr<RequestBodyCo
     Ok((s, ReferenceOr::Value(content)))
 }
 
-fn content(s: &str) -> IResult<&str, OperationContent> {
-    let (s, contents) = separated_list1(multispace1, return_statement)(s)?;
+fn content<'a>(s: &'a str, operation: &str) -> IResult<&'a str, OperationContent> {
+    let (s, contents) = separated_list1(multispace1, |s| {
+        // remember where the statement starts to point duplicates at it
+        let (rest, (code, res)) = return_statement(s)?;
+        Ok((rest, (s, code, res)))
+    })(s)?;
+
     let mut content = OperationContent::default();
-    for (code, res) in contents {
-        match code {
-            None => {
-                content.default = Some(res);
-            }
-            Some(code) => {
-                content.response.insert(code, res);
-            }
+    for (at, code, res) in contents {
+        let previous = match &code {
+            None => content.default.replace(res),
+            Some(code) => content.response.insert(code.clone(), res),
+        };
+
+        if previous.is_some() {
+            return Err(nom::Err::Failure(SyntaxError::message(
+                at,
+                format!(
+                    "duplicate `{}` response for operation `{operation}`",
+                    code.as_deref().unwrap_or("default")
+                ),
+            )));
         }
     }
 
@@ -238,12 +282,20 @@ fn content(s: &str) -> IResult<&str, OperationContent> {
 }
 
 fn return_statement(s: &str) -> IResult<&str, (Option<String>, ReferenceOr<ResponseContent>)> {
-    let (s, _) = tag("return")(s)?;
+    let (s, _) = context("`return` statement", tag("return"))(s)?;
     let (s, _) = multispace1(s)?;
-    let (s, code) = alt((tag("default"), digit1))(s)?;
+    cut(return_statement_impl)(s)
+}
+
+fn return_statement_impl(s: &str) -> IResult<&str, (Option<String>, ReferenceOr<ResponseContent>)> {
+    let (rest, code) = context("status code", alt((tag("default"), digit1)))(s)?;
     if code != "default" && code.len() != 3 {
-        return Err(nom::Err::Error(nom::error::Error::new(s, ErrorKind::Digit)));
+        return Err(nom::Err::Error(SyntaxError::expected(
+            s,
+            "three-digit status code",
+        )));
     }
+    let s = rest;
     let (s, _) = multispace1(s)?;
     let (s, res) = operation_response(s)?;
 
@@ -261,7 +313,7 @@ fn operation_response(s: &str) -> IResult<&str, ReferenceOr<ResponseContent>> {
 }
 
 fn operation_response_ref(s: &str) -> IResult<&str, ReferenceOr<ResponseContent>> {
-    let (s, r) = identifier(s)?;
+    let (s, r) = qualified_identifier(s)?;
     Ok((s, ReferenceOr::Ref(r)))
 }
 
@@ -1,15 +1,16 @@
 use crate::data::{RequestBody, RequestBodyContent};
+use crate::parser::error::IResult;
 use crate::parser::response_definition::content_assignment;
+use crate::parser::whitespace::multispace0;
 use crate::parser::with_attributes::with_attributes;
 use crate::parser::{definition_head, shorthand_definition_head};
-use nom::character::complete::{char, multispace0};
-use nom::combinator::opt;
+use nom::character::complete::char;
+use nom::combinator::{cut, opt};
 use nom::sequence::delimited;
-use nom::IResult;
 
 pub(super) fn request_body_definition(s: &str) -> IResult<&str, RequestBody> {
     let (s, name) = definition_head("requestBody")(s)?;
-    let (s, content) = request_body_content(s)?;
+    let (s, content) = cut(request_body_content)(s)?;
 
     Ok((s, RequestBody { name, content }))
 }
@@ -1,20 +1,20 @@
 use nom::bytes::complete::tag;
-use nom::character::complete::{char, multispace0};
-use nom::combinator::opt;
-use nom::error::ErrorKind;
+use nom::character::complete::char;
+use nom::combinator::{cut, opt};
 use nom::sequence::delimited;
-use nom::IResult;
 
 use crate::data::{Response, ResponseContent, TypeWithAttributes};
+use crate::parser::error::{IResult, SyntaxError};
 use crate::parser::parameter_definition::parameters_definition;
 use crate::parser::schema_definition::data_type;
+use crate::parser::whitespace::multispace0;
 use crate::parser::with_attributes::with_attributes;
 use crate::parser::{definition_head, opt_permutation, shorthand_definition_head};
 use crate::{ParameterType, Parameters};
 
 pub(super) fn response_definition(s: &str) -> IResult<&str, Response> {
     let (s, name) = definition_head("response")(s)?;
-    let (s, content) = response_contents(s)?;
+    let (s, content) = cut(response_contents)(s)?;
 
     Ok((s, Response { name, content }))
 }
@@ -46,9 +46,9 @@ fn response_contents(s: &str) -> IResult<&str, ResponseContent> {
 fn response_contents_impl(s: &str) -> IResult<&str, (Parameters, TypeWithAttributes)> {
     let (s, (ha, ca)) = opt_permutation((headers_assignment, content_assignment))(s)?;
     match ca {
-        None => Err(nom::Err::Error(nom::error::Error::new(
+        None => Err(nom::Err::Error(SyntaxError::expected(
             s,
-            ErrorKind::Permutation,
+            "`content` assignment",
         ))),
         Some(ca) => Ok((s, (ha.unwrap_or_default(), ca))),
     }
@@ -58,14 +58,14 @@ fn headers_assignment(s: &str) -> IResult<&str, Parameters> {
     let (s, _) = multispace0(s)?;
     let (s, _) = tag("headers")(s)?;
     let (s, _) = delimited(multispace0, char('='), multispace0)(s)?;
-    parameters_definition(ParameterType::Header)(s)
+    cut(parameters_definition(ParameterType::Header))(s)
 }
 
 pub(super) fn content_assignment(s: &str) -> IResult<&str, TypeWithAttributes> {
     let (s, _) = multispace0(s)?;
     let (s, _) = tag("content")(s)?;
     let (s, _) = delimited(multispace0, char('='), multispace0)(s)?;
-    let (s, t) = data_type(s)?;
+    let (s, t) = cut(data_type)(s)?;
     let (s, _) = multispace0(s)?;
     Ok((s, t))
 }
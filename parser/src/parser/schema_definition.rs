@@ -1,30 +1,47 @@
-use std::collections::HashMap;
+use indexmap::IndexMap;
 
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::{char, multispace0};
-use nom::character::streaming::multispace1;
-use nom::combinator::opt;
-use nom::multi::separated_list0;
-use nom::sequence::delimited;
-use nom::IResult;
-
-use crate::data::{Requirement, Schema, SchemaContent, SchemaField, Type, TypeWithAttributes};
+use nom::character::complete::char;
+use nom::combinator::{cut, opt};
+use nom::error::context;
+use nom::multi::{many0, separated_list0, separated_list1};
+use nom::sequence::{delimited, preceded, terminated};
+
+use crate::data::visit::{walk_type_with_attributes, VisitMut};
+use crate::data::{
+    DiscriminatedUnion, Requirement, Schema, SchemaContent, SchemaField, Type, TypeWithAttributes,
+};
 use crate::parser::enum_definition::shorthand_enum_definition;
-use crate::parser::identifier::identifier;
+use crate::parser::error::IResult;
+use crate::parser::identifier::{attribute_name, identifier, keyword, qualified_identifier};
+use crate::parser::literals::string_literal;
+use crate::parser::whitespace::{doc_comment, multispace0, multispace1};
 use crate::parser::with_attributes::with_attributes;
-use crate::parser::{definition_head, shorthand_definition_head};
+use crate::parser::{block_entry, definition_head, shorthand_definition_head, span, wrapper};
 use crate::ReferenceOr;
 
 pub(super) fn schema_definition(s: &str) -> IResult<&str, Schema> {
     let (s, name) = definition_head("schema")(s)?;
-    let (s, _) = multispace0(s)?;
-    let (s, content) = alt((brace_schema_definition, assignment_schema_definition))(s)?;
+    let (s, parameters) = opt(type_parameters)(s)?;
+    let (s, base) = opt(extends)(s)?;
+    let (s, mut content) = match base {
+        // only fields can be added to a base schema
+        Some(_) => cut(brace_schema_definition)(s)?,
+        None => cut(alt((brace_schema_definition, assignment_schema_definition)))(s)?,
+    };
+    let parameters = parameters.unwrap_or_default();
+    if !parameters.is_empty() {
+        MarkParameters(&parameters).visit_schema_content(&mut content);
+    }
 
     Ok((
         s,
         Schema {
             name: Some(name),
+            doc: None,
+            parameters,
+            base,
             content,
         },
     ))
@@ -32,24 +49,65 @@ pub(super) fn schema_definition(s: &str) -> IResult<&str, Schema> {
 
 pub(super) fn shorthand_schema_definition(s: &str) -> IResult<&str, Schema> {
     let (s, _) = shorthand_definition_head("schema")(s)?;
-    let (s, _) = multispace0(s)?;
     let (s, content) = alt((brace_schema_definition, assignment_schema_definition))(s)?;
 
     Ok((
         s,
         Schema {
             name: None,
+            doc: None,
+            parameters: vec![],
+            base: None,
             content,
         },
     ))
 }
 
+/// `<T, E>`
+fn type_parameters(s: &str) -> IResult<&str, Vec<String>> {
+    preceded(
+        multispace0,
+        delimited(
+            char('<'),
+            cut(delimited(
+                multispace0,
+                terminated(
+                    separated_list1(operator(','), identifier),
+                    opt(operator(',')),
+                ),
+                multispace0,
+            )),
+            cut(char('>')),
+        ),
+    )(s)
+}
+
+/// Turns the names of the type parameters of a generic schema into [`Type::Parameter`]s.
+struct MarkParameters<'a>(&'a [String]);
+
+impl VisitMut for MarkParameters<'_> {
+    fn visit_type_with_attributes(&mut self, twa: &mut TypeWithAttributes) {
+        match &twa.target_type {
+            Type::Schema(ReferenceOr::Ref(name)) if self.0.contains(name) => {
+                twa.target_type = Type::Parameter(name.clone());
+            }
+            _ => walk_type_with_attributes(self, twa),
+        }
+    }
+}
+
+/// `extends User`
+fn extends(s: &str) -> IResult<&str, String> {
+    let (s, _) = delimited(multispace1, keyword("extends"), multispace1)(s)?;
+    cut(qualified_identifier)(s)
+}
+
 fn brace_schema_definition(s: &str) -> IResult<&str, SchemaContent> {
     let (s, attributes) = opt(with_attributes)(s)?;
     let (s, _) = multispace0(s)?;
     let (s, fields) = delimited(
         char('{'),
-        delimited(multispace0, brace_schema_content, multispace0),
+        terminated(brace_schema_content, multispace0),
         char('}'),
     )(s)?;
 
@@ -63,11 +121,7 @@ fn brace_schema_definition(s: &str) -> IResult<&str, SchemaContent> {
 }
 
 fn brace_schema_content(s: &str) -> IResult<&str, Vec<SchemaField>> {
-    let (s, res) = delimited(
-        multispace0,
-        separated_list0(tag(","), data_content),
-        multispace0,
-    )(s)?;
+    let (s, res) = terminated(separated_list0(tag(","), data_content), multispace0)(s)?;
 
     let (s, _) = opt(char(','))(s)?;
     let (s, _) = multispace0(s)?;
@@ -76,23 +130,30 @@ fn brace_schema_content(s: &str) -> IResult<&str, Vec<SchemaField>> {
 }
 
 fn data_content(s: &str) -> IResult<&str, SchemaField> {
-    delimited(multispace0, data_content_impl, multispace0)(s)
+    let (s, doc) = doc_comment(s)?;
+    let (s, field) = terminated(data_content_impl(doc), multispace0)(s)?;
+
+    Ok((s, field))
 }
 
-fn data_content_impl(s: &str) -> IResult<&str, SchemaField> {
-    let (s, name) = identifier(s)?;
-    let (s, _) = delimited(multispace0, tag(":"), multispace0)(s)?;
-    let (s, requirement_spec) = opt(requirement_spec)(s)?;
-    let (s, ty) = data_type(s)?;
+fn data_content_impl(doc: Option<String>) -> impl Fn(&str) -> IResult<&str, SchemaField> {
+    move |start: &str| {
+        let (s, name) = identifier(start)?;
+        let (s, _) = delimited(multispace0, tag(":"), multispace0)(s)?;
+        let (s, requirement_spec) = opt(requirement_spec)(s)?;
+        let (s, ty) = cut(data_type)(s)?;
 
-    Ok((
-        s,
-        SchemaField {
-            name,
-            requirement: requirement_spec.unwrap_or_default(),
-            target_type: ty,
-        },
-    ))
+        Ok((
+            s,
+            SchemaField {
+                name,
+                span: span(start, s),
+                doc: doc.clone(),
+                requirement: requirement_spec.unwrap_or_default(),
+                target_type: ty,
+            },
+        ))
+    }
 }
 
 pub(super) fn requirement_spec(s: &str) -> IResult<&str, Requirement> {
@@ -109,22 +170,81 @@ pub(super) fn requirement_spec(s: &str) -> IResult<&str, Requirement> {
 }
 
 fn assignment_schema_definition(s: &str) -> IResult<&str, SchemaContent> {
+    let (s, _) = multispace0(s)?;
     let (s, _) = char('=')(s)?;
     let (s, _) = multispace0(s)?;
-    let (s, dt) = data_type(s)?;
+    let (s, dt) = cut(data_type)(s)?;
 
     Ok((s, SchemaContent::Typedef(Box::new(dt))))
 }
 
 pub(super) fn data_type(s: &str) -> IResult<&str, TypeWithAttributes> {
+    context("data type", one_of_type)(s)
+}
+
+/// `A | B | C`, where `&` binds tighter than `|`
+fn one_of_type(s: &str) -> IResult<&str, TypeWithAttributes> {
+    let (s, first) = all_of_type(s)?;
+    let (s, rest) = many0(preceded(operator('|'), cut(all_of_type)))(s)?;
+
+    Ok((s, composed(first, rest, Type::OneOf)))
+}
+
+/// `A & B & C`
+fn all_of_type(s: &str) -> IResult<&str, TypeWithAttributes> {
+    let (s, first) = data_type_impl(s)?;
+    let (s, rest) = many0(preceded(operator('&'), cut(data_type_impl)))(s)?;
+
+    Ok((s, composed(first, rest, Type::AllOf)))
+}
+
+fn operator(op: char) -> impl Fn(&str) -> IResult<&str, char> {
+    move |s| delimited(multispace0, char(op), multispace0)(s)
+}
+
+fn composed(
+    first: TypeWithAttributes,
+    rest: Vec<TypeWithAttributes>,
+    compose: fn(Vec<TypeWithAttributes>) -> Type,
+) -> TypeWithAttributes {
+    if rest.is_empty() {
+        return first;
+    }
+
+    TypeWithAttributes {
+        target_type: compose([first].into_iter().chain(rest).collect()),
+        attributes: IndexMap::new(),
+    }
+}
+
+fn data_type_impl(s: &str) -> IResult<&str, TypeWithAttributes> {
+    alt((
+        builtin_data_type,
+        list_type,
+        discriminated_type,
+        composition_type("oneOf", Type::OneOf),
+        composition_type("anyOf", Type::AnyOf),
+        composition_type("allOf", Type::AllOf),
+        parenthesized_type,
+        shorthand_schema_type,
+        shorthand_enum_type,
+        instance_type,
+        schema_name_type,
+    ))(s)
+}
+
+fn builtin_data_type(s: &str) -> IResult<&str, TypeWithAttributes> {
     alt((
         primitive_data_type("String", Type::string()),
         primitive_data_type("Object", Type::Object),
         primitive_data_type("Bool", Type::Bool),
         primitive_data_type("Int32", Type::int32()),
         primitive_data_type("Int64", Type::int64()),
-        primitive_data_type("Float", Type::Float),
-        primitive_data_type("Number", Type::Float),
+        primitive_data_type("Float", Type::float()),
+        primitive_data_type("Float32", Type::float()),
+        primitive_data_type("Float64", Type::double()),
+        primitive_data_type("Double", Type::double()),
+        primitive_data_type("Number", Type::number()),
         primitive_data_type("Int", Type::int()),
         primitive_data_type("DateTime", Type::datetime()),
         primitive_data_type("Date", Type::date()),
@@ -133,10 +253,6 @@ pub(super) fn data_type(s: &str) -> IResult<&str, TypeWithAttributes> {
         primitive_data_type("Email", Type::email()),
         primitive_data_type("Uuid", Type::uuid()),
         primitive_data_type("Uri", Type::uri()),
-        list_type,
-        shorthand_schema_type,
-        shorthand_enum_type,
-        schema_name_type,
     ))(s)
 }
 
@@ -145,7 +261,7 @@ fn primitive_data_type(
     out: Type,
 ) -> impl Fn(&str) -> IResult<&str, TypeWithAttributes> {
     move |s| {
-        let (s, _) = tag(ty)(s)?;
+        let (s, _) = keyword(ty)(s)?;
         let (s, attributes) = opt(with_attributes)(s)?;
         let wta = TypeWithAttributes {
             target_type: out.clone(),
@@ -156,12 +272,41 @@ fn primitive_data_type(
 }
 
 fn schema_name_type(s: &str) -> IResult<&str, TypeWithAttributes> {
-    let (s, identifier) = identifier(s)?;
+    let (s, identifier) = qualified_identifier(s)?;
     Ok((
         s,
         TypeWithAttributes {
             target_type: Type::Schema(ReferenceOr::Ref(identifier)),
-            attributes: HashMap::new(),
+            attributes: IndexMap::new(),
+        },
+    ))
+}
+
+/// `Page<User>` or `Result<User, Error>`
+fn instance_type(s: &str) -> IResult<&str, TypeWithAttributes> {
+    let (s, name) = qualified_identifier(s)?;
+    let (s, arguments) = preceded(
+        multispace0,
+        delimited(
+            char('<'),
+            cut(delimited(
+                multispace0,
+                terminated(
+                    separated_list1(operator(','), data_type),
+                    opt(operator(',')),
+                ),
+                multispace0,
+            )),
+            cut(char('>')),
+        ),
+    )(s)?;
+    let (s, attributes) = opt(with_attributes)(s)?;
+
+    Ok((
+        s,
+        TypeWithAttributes {
+            target_type: Type::Instance { name, arguments },
+            attributes: attributes.unwrap_or_default(),
         },
     ))
 }
@@ -172,7 +317,7 @@ fn shorthand_schema_type(s: &str) -> IResult<&str, TypeWithAttributes> {
         s,
         TypeWithAttributes {
             target_type: Type::Schema(ReferenceOr::Value(schema.content)),
-            attributes: HashMap::new(),
+            attributes: IndexMap::new(),
         },
     ))
 }
@@ -183,21 +328,100 @@ fn shorthand_enum_type(s: &str) -> IResult<&str, TypeWithAttributes> {
         s,
         TypeWithAttributes {
             target_type: Type::Enum(en),
-            attributes: HashMap::new(),
+            attributes: IndexMap::new(),
+        },
+    ))
+}
+
+/// `oneOf<A, B>`, `anyOf<A, B>` or `allOf<A, B>`
+fn composition_type(
+    name: &'static str,
+    compose: fn(Vec<TypeWithAttributes>) -> Type,
+) -> impl Fn(&str) -> IResult<&str, TypeWithAttributes> {
+    move |s| {
+        let (s, _) = keyword(name)(s)?;
+        let (s, types) = preceded(
+            multispace0,
+            delimited(
+                char('<'),
+                cut(delimited(
+                    multispace0,
+                    terminated(
+                        separated_list1(operator(','), data_type),
+                        opt(operator(',')),
+                    ),
+                    multispace0,
+                )),
+                cut(char('>')),
+            ),
+        )(s)?;
+        let (s, attributes) = opt(with_attributes)(s)?;
+
+        Ok((
+            s,
+            TypeWithAttributes {
+                target_type: compose(types),
+                attributes: attributes.unwrap_or_default(),
+            },
+        ))
+    }
+}
+
+/// `oneOf by "kind" { card: CardPayment, bank: BankPayment }`
+fn discriminated_type(s: &str) -> IResult<&str, TypeWithAttributes> {
+    let (s, _) = keyword("oneOf")(s)?;
+    let (s, _) = delimited(multispace1, keyword("by"), multispace1)(s)?;
+    let (s, property) = cut(string_literal)(s)?;
+    let (s, _) = multispace0(s)?;
+    let (s, mapping) = cut(delimited(
+        char('{'),
+        many0(block_entry(discriminator_mapping)),
+        preceded(multispace0, char('}')),
+    ))(s)?;
+    let (s, attributes) = opt(with_attributes)(s)?;
+
+    Ok((
+        s,
+        TypeWithAttributes {
+            target_type: Type::Discriminated(DiscriminatedUnion {
+                property: property.to_string(),
+                mapping,
+            }),
+            attributes: attributes.unwrap_or_default(),
         },
     ))
 }
 
+/// `card: CardPayment`, where the value may be quoted like `"credit-card"`
+fn discriminator_mapping(s: &str) -> IResult<&str, (String, String)> {
+    let (s, value) = alt((
+        wrapper(string_literal, |v: &str| v.to_string()),
+        attribute_name,
+    ))(s)?;
+    let (s, _) = cut(delimited(multispace0, char(':'), multispace0))(s)?;
+    let (s, schema) = cut(qualified_identifier)(s)?;
+
+    Ok((s, (value, schema)))
+}
+
+/// `(A | B)`, to compose a composition
+fn parenthesized_type(s: &str) -> IResult<&str, TypeWithAttributes> {
+    delimited(
+        char('('),
+        cut(delimited(multispace0, data_type, multispace0)),
+        cut(char(')')),
+    )(s)
+}
+
 fn list_type(s: &str) -> IResult<&str, TypeWithAttributes> {
     let (s, _) = tag("List")(s)?;
-    let (s, item_type) = delimited(
+    let (s, item_type) = preceded(
         multispace0,
         delimited(
             char('<'),
             delimited(multispace0, data_type, multispace0),
             char('>'),
         ),
-        multispace0,
     )(s)?;
     let (s, attributes) = opt(with_attributes)(s)?;
 
@@ -211,3 +435,119 @@ fn list_type(s: &str) -> IResult<&str, TypeWithAttributes> {
         },
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_source;
+
+    fn parse_type(s: &str) -> Type {
+        let (rest, ty) = parse_source(data_type, s).unwrap();
+        assert_eq!(rest, "", "`{s}` was not parsed to the end");
+        ty.target_type
+    }
+
+    fn names(types: &[TypeWithAttributes]) -> Vec<String> {
+        types
+            .iter()
+            .map(|t| match &t.target_type {
+                Type::Schema(ReferenceOr::Ref(name)) => name.clone(),
+                Type::OneOf(_) => "oneOf".to_string(),
+                Type::AllOf(_) => "allOf".to_string(),
+                other => format!("{other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn all_of_binds_tighter_than_one_of() {
+        let Type::OneOf(types) = parse_type("A | B & C | D") else {
+            panic!("not a oneOf");
+        };
+        assert_eq!(names(&types), ["A", "allOf", "D"]);
+        assert!(matches!(&types[1].target_type, Type::AllOf(all) if names(all) == ["B", "C"]));
+    }
+
+    #[test]
+    fn parentheses_group_compositions() {
+        let Type::AllOf(types) = parse_type("(A | B) & C") else {
+            panic!("not an allOf");
+        };
+        assert_eq!(names(&types), ["oneOf", "C"]);
+    }
+
+    #[test]
+    fn composition_keywords() {
+        assert!(matches!(parse_type("anyOf<A, B,>"), Type::AnyOf(t) if names(&t) == ["A", "B"]));
+        assert!(matches!(parse_type("oneOf<A, List<B>>"), Type::OneOf(t) if t.len() == 2));
+        assert!(matches!(parse_type("allOf<A>"), Type::AllOf(t) if names(&t) == ["A"]));
+    }
+
+    #[test]
+    fn discriminated_union() {
+        let Type::Discriminated(union) =
+            parse_type(r#"oneOf by "kind" { card: CardPayment, "bank-transfer": bank.Transfer }"#)
+        else {
+            panic!("not a discriminated union");
+        };
+        assert_eq!(union.property, "kind");
+        assert_eq!(
+            union.mapping,
+            [
+                ("card".to_string(), "CardPayment".to_string()),
+                ("bank-transfer".to_string(), "bank.Transfer".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn discriminated_union_needs_a_property() {
+        assert!(matches!(
+            parse_source(data_type, "oneOf by { card: CardPayment }"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+
+    #[test]
+    fn extends_a_base_schema() {
+        let (_, schema) = parse_source(
+            schema_definition,
+            "schema Admin extends User { role: String }",
+        )
+        .unwrap();
+        assert_eq!(schema.base.as_deref(), Some("User"));
+        assert!(parse_source(schema_definition, "schema Admin extends User = String").is_err());
+    }
+
+    #[test]
+    fn instances_of_generic_schemas() {
+        let Type::Instance { name, arguments } = parse_type("Result<User, List<Error>>") else {
+            panic!("not an instance");
+        };
+        assert_eq!(name, "Result");
+        assert_eq!(arguments.len(), 2);
+        assert!(matches!(&arguments[1].target_type, Type::List { .. }));
+    }
+
+    #[test]
+    fn type_parameters_are_marked() {
+        let (_, schema) = parse_source(
+            schema_definition,
+            "schema Page<T> { items: List<T>, total: Int, next: Page<T> }",
+        )
+        .unwrap();
+        assert_eq!(schema.parameters, ["T"]);
+
+        let SchemaContent::Definition { fields, .. } = schema.content else {
+            panic!("not a definition");
+        };
+        let Type::List { item_type } = &fields[0].target_type.target_type else {
+            panic!("not a list");
+        };
+        assert!(matches!(&item_type.target_type, Type::Parameter(p) if p == "T"));
+        let Type::Instance { arguments, .. } = &fields[2].target_type.target_type else {
+            panic!("not an instance");
+        };
+        assert!(matches!(&arguments[0].target_type, Type::Parameter(p) if p == "T"));
+    }
+}
@@ -0,0 +1,71 @@
+use nom::branch::alt;
+use nom::character::complete::char;
+use nom::combinator::{cut, opt};
+use nom::error::context;
+use nom::multi::many0;
+use nom::sequence::{delimited, preceded};
+
+use crate::data::{Attributes, SecurityScheme, SecuritySchemeType, Value};
+use crate::parser::error::IResult;
+use crate::parser::identifier::{attribute_name, keyword};
+use crate::parser::info_definition::default_spec;
+use crate::parser::literals::string_literal;
+use crate::parser::value::value;
+use crate::parser::whitespace::multispace0;
+use crate::parser::{block_entry, definition_head, wrapper};
+
+pub(super) fn security_definition(s: &str) -> IResult<&str, SecurityScheme> {
+    let (s, default_spec) = opt(default_spec)(s)?;
+    let (s, name) = definition_head("security")(s)?;
+    let (s, _) = cut(delimited(multispace0, char('='), multispace0))(s)?;
+    let (s, scheme_type) = cut(context("security scheme type", scheme_type))(s)?;
+    let (s, _) = multispace0(s)?;
+    let (s, fields) = cut(block)(s)?;
+
+    Ok((
+        s,
+        SecurityScheme {
+            name,
+            is_default: default_spec.is_some(),
+            doc: None,
+            scheme_type,
+            fields,
+        },
+    ))
+}
+
+fn scheme_type(s: &str) -> IResult<&str, SecuritySchemeType> {
+    alt((
+        wrapper(keyword("http"), |_| SecuritySchemeType::Http),
+        wrapper(keyword("apiKey"), |_| SecuritySchemeType::ApiKey),
+        wrapper(keyword("oauth2"), |_| SecuritySchemeType::OAuth2),
+        wrapper(keyword("openIdConnect"), |_| {
+            SecuritySchemeType::OpenIdConnect
+        }),
+    ))(s)
+}
+
+/// `{ key = value; nested { ... } }`, where keys may be quoted like `"read:users"`.
+fn block(s: &str) -> IResult<&str, Attributes> {
+    let (s, entries) = delimited(
+        char('{'),
+        many0(block_entry(field)),
+        preceded(multispace0, char('}')),
+    )(s)?;
+
+    Ok((s, entries.into_iter().collect()))
+}
+
+fn field(s: &str) -> IResult<&str, (String, Value)> {
+    let (s, key) = alt((
+        wrapper(string_literal, |k: &str| k.to_string()),
+        attribute_name,
+    ))(s)?;
+    let (s, _) = multispace0(s)?;
+    let (s, value) = cut(alt((
+        preceded(char('='), preceded(multispace0, value)),
+        wrapper(block, |fields| Value::Object(fields.into_iter().collect())),
+    )))(s)?;
+
+    Ok((s, (key, value)))
+}
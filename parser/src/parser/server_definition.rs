@@ -0,0 +1,131 @@
+use indexmap::IndexMap;
+use nom::branch::alt;
+use nom::character::complete::char;
+use nom::combinator::{cut, opt};
+use nom::multi::many0;
+use nom::sequence::{delimited, preceded};
+
+use crate::data::{Server, ServerVariable};
+use crate::parser::error::{IResult, SyntaxError};
+use crate::parser::identifier::{identifier, keyword};
+use crate::parser::literals::string_literal;
+use crate::parser::whitespace::multispace0;
+use crate::parser::with_attributes::with_attributes;
+use crate::parser::{block_entry, definition_head};
+
+enum Entry {
+    Url(String),
+    Description(String),
+    Variables(IndexMap<String, ServerVariable>),
+}
+
+pub(super) fn server_definition(s: &str) -> IResult<&str, Server> {
+    let (s, name) = definition_head("server")(s)?;
+    let (s, attributes) = opt(with_attributes)(s)?;
+    let (s, _) = multispace0(s)?;
+    let (s, entries) = cut(preceded(
+        char('{'),
+        many0(block_entry(|s| {
+            // remember where the entry starts to point repeated ones at it
+            let (rest, entry) = server_entry(s)?;
+            Ok((rest, (s, entry)))
+        })),
+    ))(s)?;
+    let (s, _) = multispace0(s)?;
+
+    let mut url = None;
+    let mut description = None;
+    let mut variables = IndexMap::new();
+    for (at, e) in entries {
+        let (key, set) = match e {
+            Entry::Url(u) => ("url", url.replace(u).is_none()),
+            Entry::Description(d) => ("description", description.replace(d).is_none()),
+            Entry::Variables(v) => {
+                variables.extend(v);
+                continue;
+            }
+        };
+        if !set {
+            return Err(nom::Err::Failure(SyntaxError::message(
+                at,
+                format!("`{key}` is set more than once in server `{name}`"),
+            )));
+        }
+    }
+    let Some(url) = url else {
+        return Err(nom::Err::Failure(SyntaxError::expected(
+            s,
+            "`url = \"...\"`",
+        )));
+    };
+    let (s, _) = cut(char('}'))(s)?;
+
+    Ok((
+        s,
+        Server {
+            name,
+            url,
+            description,
+            variables,
+            attributes: attributes.unwrap_or_default(),
+        },
+    ))
+}
+
+fn server_entry(s: &str) -> IResult<&str, Entry> {
+    alt((
+        |s| {
+            let (s, url) = string_assignment("url")(s)?;
+            Ok((s, Entry::Url(url)))
+        },
+        |s| {
+            let (s, description) = string_assignment("description")(s)?;
+            Ok((s, Entry::Description(description)))
+        },
+        |s| {
+            let (s, variables) = variables(s)?;
+            Ok((s, Entry::Variables(variables)))
+        },
+    ))(s)
+}
+
+fn string_assignment(name: &'static str) -> impl Fn(&str) -> IResult<&str, String> {
+    move |s| {
+        let (s, _) = keyword(name)(s)?;
+        let (s, _) = cut(delimited(multispace0, char('='), multispace0))(s)?;
+        let (s, value) = cut(string_literal)(s)?;
+
+        Ok((s, value.to_string()))
+    }
+}
+
+fn variables(s: &str) -> IResult<&str, IndexMap<String, ServerVariable>> {
+    let (s, _) = keyword("variables")(s)?;
+    let (s, _) = multispace0(s)?;
+    let (s, variables) = cut(delimited(
+        char('{'),
+        many0(block_entry(variable)),
+        preceded(multispace0, char('}')),
+    ))(s)?;
+
+    Ok((s, variables.into_iter().collect()))
+}
+
+/// `name = "default"`, optionally followed by attributes such as `enum` and `description`.
+fn variable(s: &str) -> IResult<&str, (String, ServerVariable)> {
+    let (s, name) = identifier(s)?;
+    let (s, _) = cut(delimited(multispace0, char('='), multispace0))(s)?;
+    let (s, default) = cut(string_literal)(s)?;
+    let (s, attributes) = opt(with_attributes)(s)?;
+
+    Ok((
+        s,
+        (
+            name,
+            ServerVariable {
+                default: default.to_string(),
+                attributes: attributes.unwrap_or_default(),
+            },
+        ),
+    ))
+}
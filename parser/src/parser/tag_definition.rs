@@ -1,8 +1,8 @@
 use nom::combinator::opt;
-use nom::IResult;
 
 use crate::data::Tag;
 use crate::parser::definition_head;
+use crate::parser::error::IResult;
 use crate::parser::with_attributes::with_attributes;
 
 pub(super) fn tag_definition(s: &str) -> IResult<&str, Tag> {
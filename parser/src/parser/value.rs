@@ -1,13 +1,101 @@
 use crate::data::Value;
-use crate::parser::identifier::identifier;
-use crate::parser::literals::literal;
+use crate::parser::error::IResult;
+use crate::parser::identifier::{attribute_name, qualified_identifier};
+use crate::parser::literals::{literal, string_literal};
+use crate::parser::whitespace::multispace0;
 use crate::parser::wrapper;
 use nom::branch::alt;
-use nom::IResult;
+use nom::character::complete::char;
+use nom::combinator::opt;
+use nom::error::context;
+use nom::multi::separated_list0;
+use nom::sequence::{delimited, separated_pair, terminated};
 
 pub(super) fn value(s: &str) -> IResult<&str, Value> {
-    alt((
-        wrapper(literal, Value::Immediate),
-        wrapper(identifier, Value::Identifier),
-    ))(s)
+    context(
+        "value",
+        alt((
+            wrapper(literal, Value::Immediate),
+            wrapper(qualified_identifier, Value::Identifier),
+            wrapper(list_of(value), Value::List),
+            wrapper(object_of(value), Value::Object),
+        )),
+    )(s)
+}
+
+/// `[a, b, c]`; a trailing comma is allowed.
+pub(super) fn list_of<'a, O, F>(item: F) -> impl Fn(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Fn(&'a str) -> IResult<&'a str, O> + Copy,
+{
+    move |s| {
+        delimited(
+            terminated(char('['), multispace0),
+            comma_separated(item),
+            char(']'),
+        )(s)
+    }
+}
+
+/// `{ key: a, "x-key": b }`; a trailing comma is allowed.
+pub(super) fn object_of<'a, O, F>(item: F) -> impl Fn(&'a str) -> IResult<&'a str, Vec<(String, O)>>
+where
+    F: Fn(&'a str) -> IResult<&'a str, O> + Copy,
+{
+    move |s| {
+        let entry = separated_pair(
+            alt((
+                wrapper(string_literal, |k: &str| k.to_string()),
+                attribute_name,
+            )),
+            delimited(multispace0, char(':'), multispace0),
+            item,
+        );
+        delimited(
+            terminated(char('{'), multispace0),
+            comma_separated(entry),
+            char('}'),
+        )(s)
+    }
+}
+
+fn comma_separated<'a, O, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: FnMut(&'a str) -> IResult<&'a str, O>,
+{
+    terminated(
+        separated_list0(delimited(multispace0, char(','), multispace0), item),
+        terminated(multispace0, opt(terminated(char(','), multispace0))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Literal;
+
+    #[test]
+    fn lists_and_objects_hold_any_value() {
+        let (rest, v) = value(r#"[1, Name, { "x-key": [true], key: null }, ]"#).unwrap();
+        assert_eq!(rest, "");
+
+        let Value::List(items) = v else {
+            panic!("not a list: {v:?}");
+        };
+        assert!(matches!(items[0], Value::Immediate(Literal::Int(1))));
+        assert!(matches!(&items[1], Value::Identifier(name) if name == "Name"));
+        let Value::Object(entries) = &items[2] else {
+            panic!("not an object: {:?}", items[2]);
+        };
+        assert_eq!(entries[0].0, "x-key");
+        assert!(matches!(&entries[0].1, Value::List(v) if v.len() == 1));
+        assert_eq!(entries[1].0, "key");
+        assert!(matches!(entries[1].1, Value::Immediate(Literal::Null)));
+    }
+
+    #[test]
+    fn qualified_names() {
+        let (_, v) = value("errors.NotFound").unwrap();
+        assert!(matches!(v, Value::Identifier(name) if name == "errors.NotFound"));
+    }
 }
@@ -0,0 +1,110 @@
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_while};
+use nom::character::complete::char;
+use nom::combinator::{map, not, recognize};
+use nom::error::context;
+use nom::multi::{many0, many0_count, many1_count};
+use nom::sequence::pair;
+
+use crate::parser::error::{IResult, SyntaxError};
+
+/// Same as nom's `multispace0`, but comments are skipped as whitespace too.
+pub(super) fn multispace0(s: &str) -> IResult<&str, &str> {
+    recognize(many0_count(whitespace_or_comment))(s)
+}
+
+/// Same as nom's `multispace1`, but comments are skipped as whitespace too.
+pub(super) fn multispace1(s: &str) -> IResult<&str, &str> {
+    context("whitespace", recognize(many1_count(whitespace_or_comment)))(s)
+}
+
+/// Skips whitespace and comments like `multispace0`, collecting the text of the
+/// `///` doc comments on the way.
+pub(super) fn doc_comment(s: &str) -> IResult<&str, Option<String>> {
+    let (s, pieces) = many0(alt((
+        map(doc_comment_line, Some),
+        map(whitespace_or_comment, |_| None),
+    )))(s)?;
+
+    let lines = pieces.into_iter().flatten().collect::<Vec<_>>();
+    let doc = lines.join("\n").trim_end().to_string();
+
+    Ok((s, if doc.is_empty() { None } else { Some(doc) }))
+}
+
+fn doc_comment_line(s: &str) -> IResult<&str, &str> {
+    let (s, _) = tag("///")(s)?;
+    // `////...` is an ordinary comment, e.g. a separator line
+    let (s, _) = not(char('/'))(s)?;
+    let (s, text) = take_while(|c| c != '\n')(s)?;
+    let text = text.trim_end_matches('\r');
+
+    Ok((s, text.strip_prefix(' ').unwrap_or(text)))
+}
+
+fn whitespace_or_comment(s: &str) -> IResult<&str, &str> {
+    alt((
+        nom::character::complete::multispace1,
+        line_comment,
+        block_comment,
+    ))(s)
+}
+
+fn line_comment(s: &str) -> IResult<&str, &str> {
+    recognize(pair(tag("//"), take_while(|c| c != '\n')))(s)
+}
+
+fn block_comment(s: &str) -> IResult<&str, &str> {
+    let (body, _) = tag("/*")(s)?;
+    match body.find("*/") {
+        Some(end) => {
+            let len = s.len() - body.len() + end + 2;
+            Ok((&s[len..], &s[..len]))
+        }
+        None => Err(nom::Err::Failure(SyntaxError::expected(
+            &s[s.len()..],
+            "`*/` closing the block comment",
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comments_are_whitespace() {
+        let (rest, _) = multispace0("  // line\n  /* block\n spanning lines */ schema").unwrap();
+        assert_eq!(rest, "schema");
+    }
+
+    #[test]
+    fn multispace1_needs_whitespace_or_a_comment() {
+        assert!(multispace1("schema").is_err());
+        assert_eq!(multispace1("/**/schema").unwrap().0, "schema");
+    }
+
+    #[test]
+    fn unterminated_block_comment_is_a_failure() {
+        assert!(matches!(
+            multispace0("/* never closed"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+
+    #[test]
+    fn doc_comment_collects_doc_lines_only() {
+        let (rest, doc) =
+            doc_comment("/// First line\n// not documentation\n///   indented\n////////\nschema")
+                .unwrap();
+        assert_eq!(rest, "schema");
+        assert_eq!(doc.as_deref(), Some("First line\n  indented"));
+    }
+
+    #[test]
+    fn doc_comment_without_doc_lines_is_none() {
+        let (rest, doc) = doc_comment("  // comment\nschema").unwrap();
+        assert_eq!(rest, "schema");
+        assert_eq!(doc, None);
+    }
+}
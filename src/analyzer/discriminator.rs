@@ -0,0 +1,99 @@
+use crate::analyzer::symbol::SymbolTable;
+use crate::diagnostic::Diagnostic;
+use parser::{ReferenceOr, Requirement, Schema, SchemaContent, SchemaField, Type};
+use std::collections::HashSet;
+
+/// Checks that the variant `schema` of a discriminated union has `property` as a required
+/// string or enum field, so that the value of the property tells the variants apart.
+///
+/// Undefined schemas are left to the resolver.
+pub(super) fn check_variant(
+    symbols: &SymbolTable,
+    schema: &str,
+    property: &str,
+) -> Option<Diagnostic> {
+    let variant = symbols.schema(schema)?;
+    let problem = match inherited_property(symbols, variant, property, &mut HashSet::new()) {
+        None => format!("schema `{schema}` does not have the discriminator property `{property}`"),
+        Some(f) if f.requirement == Requirement::Optional => {
+            format!("discriminator property `{property}` of schema `{schema}` is optional")
+        }
+        Some(f) if !is_string(symbols, &f.target_type.target_type, &mut HashSet::new()) => {
+            format!(
+                "discriminator property `{property}` of schema `{schema}` is not a string or an enum"
+            )
+        }
+        Some(_) => return None,
+    };
+
+    Some(Diagnostic::error(problem).with_note(format!(
+        "every variant of `oneOf by \"{property}\"` needs `{property}` as a required string or enum field"
+    )))
+}
+
+/// Field `property` of a named schema or the schemas it extends.
+fn inherited_property<'a>(
+    symbols: &'a SymbolTable,
+    schema: &'a Schema,
+    property: &str,
+    visited: &mut HashSet<&'a str>,
+) -> Option<&'a SchemaField> {
+    find_property(symbols, &schema.content, property, visited).or_else(|| match &schema.base {
+        Some(base) if visited.insert(base) => {
+            inherited_property(symbols, symbols.schema(base)?, property, visited)
+        }
+        _ => None,
+    })
+}
+
+/// Field `property` of a schema, looking through named schemas and `allOf` compositions.
+fn find_property<'a>(
+    symbols: &'a SymbolTable,
+    content: &'a SchemaContent,
+    property: &str,
+    visited: &mut HashSet<&'a str>,
+) -> Option<&'a SchemaField> {
+    match content {
+        SchemaContent::Definition { fields, .. } => fields.iter().find(|f| f.name == property),
+        SchemaContent::Typedef(twa) => {
+            property_of_type(symbols, &twa.target_type, property, visited)
+        }
+    }
+}
+
+fn property_of_type<'a>(
+    symbols: &'a SymbolTable,
+    ty: &'a Type,
+    property: &str,
+    visited: &mut HashSet<&'a str>,
+) -> Option<&'a SchemaField> {
+    match ty {
+        Type::Schema(ReferenceOr::Ref(name)) if visited.insert(name) => {
+            inherited_property(symbols, symbols.schema(name)?, property, visited)
+        }
+        Type::Schema(ReferenceOr::Value(content)) => {
+            find_property(symbols, content, property, visited)
+        }
+        Type::AllOf(types) => types
+            .iter()
+            .find_map(|ty| property_of_type(symbols, &ty.target_type, property, visited)),
+        _ => None,
+    }
+}
+
+/// Whether values of `ty` are strings, looking through named schemas.
+fn is_string<'a>(symbols: &'a SymbolTable, ty: &'a Type, visited: &mut HashSet<&'a str>) -> bool {
+    match ty {
+        Type::String { .. } | Type::Enum(_) => true,
+        Type::Schema(ReferenceOr::Ref(name)) if visited.insert(name) => {
+            match symbols.schema(name).map(|s| &s.content) {
+                Some(SchemaContent::Typedef(twa)) => is_string(symbols, &twa.target_type, visited),
+                _ => false,
+            }
+        }
+        Type::Schema(ReferenceOr::Value(SchemaContent::Typedef(twa))) => {
+            is_string(symbols, &twa.target_type, visited)
+        }
+        _ => false,
+    }
+}
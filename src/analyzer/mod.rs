@@ -0,0 +1,82 @@
+use crate::analyzer::path::check_paths;
+use crate::analyzer::resolve::Resolver;
+use crate::analyzer::symbol::{Kind, SymbolTable};
+use crate::diagnostic::Diagnostic;
+use parser::{Object, Span, Spanned};
+use std::collections::HashMap;
+
+mod discriminator;
+mod path;
+mod resolve;
+mod symbol;
+
+pub(crate) use symbol::{defined_name, defined_schema, server_attributes};
+
+/// Attribute keys naming the tags of an operation.
+pub(crate) const TAG_KEYS: [&str; 2] = ["tag", "tags"];
+
+/// Attribute key of the security requirements of an operation.
+pub(crate) const SECURITY_KEY: &str = "security";
+
+/// Checks the objects of all the source files before they are converted, and replaces the
+/// identifiers in attribute values with what they name.
+///
+/// Returns the errors and warnings found, in the order of the objects.
+pub(crate) fn analyze(objects: &mut [Spanned<Object>]) -> Vec<Diagnostic> {
+    let symbols = SymbolTable::new(objects);
+
+    let mut diagnostics = duplicates(objects);
+    diagnostics.append(&mut check_paths(objects));
+
+    let mut resolver = Resolver::new(&symbols);
+    for o in objects.iter_mut() {
+        resolver.object(o);
+    }
+    diagnostics.append(&mut resolver.diagnostics);
+
+    for o in objects.iter() {
+        let Some((name, kind)) = defined_name(&o.value) else {
+            continue;
+        };
+        // servers are listed in the output unless the info config selects others
+        if kind == Kind::Server {
+            continue;
+        }
+        if !resolver.used.contains(&(name.to_string(), kind)) {
+            diagnostics.push(
+                Diagnostic::warning(format!("{kind} `{name}` is never used")).with_span(&o.span),
+            );
+        }
+    }
+
+    diagnostics
+}
+
+/// Names defined more than once in the same namespace, which would overwrite each other in
+/// the output.
+fn duplicates(objects: &[Spanned<Object>]) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let mut defined = HashMap::<(String, String), &Span>::new();
+
+    for o in objects {
+        let (name, what) = match (&o.value, defined_name(&o.value)) {
+            (_, Some((name, kind))) => (name.to_string(), kind.to_string()),
+            (Object::Path(p), None) => (p.name.clone(), "path".to_string()),
+            (Object::Info(i), None) => (i.config_name.clone(), "info config".to_string()),
+            _ => continue,
+        };
+
+        match defined.get(&(name.clone(), what.clone())) {
+            None => {
+                defined.insert((name, what), &o.span);
+            }
+            Some(first) => diagnostics.push(
+                Diagnostic::error(format!("{what} `{name}` is defined more than once"))
+                    .with_span(&o.span)
+                    .with_note(format!("first defined at {first}")),
+            ),
+        }
+    }
+
+    diagnostics
+}
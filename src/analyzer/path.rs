@@ -0,0 +1,163 @@
+use crate::diagnostic::Diagnostic;
+use parser::{Object, Parameter, ParameterType, Parameters, Path, Requirement, Span, Spanned};
+use std::collections::HashMap;
+
+/// Checks that the parameters in the path templates match the declared path parameters, and
+/// that no two paths differ only in the names of their parameters.
+pub(super) fn check_paths(objects: &[Spanned<Object>]) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let mut shapes = HashMap::<String, (&str, &Span)>::new();
+
+    for o in objects {
+        let Object::Path(p) = &o.value else {
+            continue;
+        };
+
+        let names = match template_parameters(&p.name) {
+            Ok(names) => names,
+            Err(d) => {
+                diagnostics.push(d.with_span(&o.span));
+                continue;
+            }
+        };
+        diagnostics.extend(
+            path_parameters(p, &names)
+                .into_iter()
+                .map(|d| d.or_span(&o.span)),
+        );
+
+        match shapes.get(&shape(&p.name)) {
+            Some((other, first)) if *other != p.name => diagnostics.push(
+                Diagnostic::error(format!(
+                    "paths `{other}` and `{}` differ only in parameter names",
+                    p.name
+                ))
+                .with_span(&o.span)
+                .with_note(format!("`{other}` is defined at {first}")),
+            ),
+            Some(_) => {}
+            None => {
+                shapes.insert(shape(&p.name), (&p.name, &o.span));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// Names of the `{parameter}`s in a path template, in order.
+fn template_parameters(path: &str) -> Result<Vec<&str>, Diagnostic> {
+    let mut names = vec![];
+    let mut rest = path;
+
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err(Diagnostic::error(format!(
+                "unmatched `}}` in path `{path}`"
+            )));
+        }
+        let after = &rest[start + 1..];
+        let end = after
+            .find(['{', '}'])
+            .filter(|end| after[*end..].starts_with('}'))
+            .ok_or_else(|| Diagnostic::error(format!("unclosed `{{` in path `{path}`")))?;
+
+        let name = &after[..end];
+        if name.is_empty() {
+            return Err(Diagnostic::error(format!(
+                "empty parameter name in path `{path}`"
+            )));
+        }
+        if names.contains(&name) {
+            return Err(Diagnostic::error(format!(
+                "parameter `{name}` appears more than once in path `{path}`"
+            )));
+        }
+        names.push(name);
+        rest = &after[end + 1..];
+    }
+
+    Ok(names)
+}
+
+/// The path template with its parameter names erased, e.g. `/users/{}` for `/users/{id}`.
+fn shape(path: &str) -> String {
+    let mut shape = String::new();
+    let mut in_parameter = false;
+    for c in path.chars() {
+        match c {
+            '{' => {
+                in_parameter = true;
+                shape.push('{');
+            }
+            '}' => {
+                in_parameter = false;
+                shape.push('}');
+            }
+            c if !in_parameter => shape.push(c),
+            _ => {}
+        }
+    }
+    shape
+}
+
+/// Path parameters are declared on the path or on each operation, and every operation has to
+/// declare exactly the parameters of the template as required parameters.
+fn path_parameters(path: &Path, names: &[&str]) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+
+    let shared = of_path(&path.content.parameters);
+
+    for p in &shared {
+        diagnostics.extend(declared_parameter(&path.name, names, p));
+    }
+
+    for op in path.content.operations() {
+        let own = of_path(&op.parameters);
+        for p in &own {
+            diagnostics.extend(declared_parameter(&path.name, names, p));
+        }
+
+        for name in names {
+            let declared = shared.iter().chain(&own).any(|p| p.name == *name);
+            if !declared {
+                diagnostics.push(
+                    Diagnostic::error(format!(
+                        "operation `{}` does not declare path parameter `{name}`",
+                        op.name
+                    ))
+                    .with_span(&op.span)
+                    .with_note(format!(
+                        "declare it with `pathParameters = parameters {{ {name}: ... }}` on the path or the operation"
+                    )),
+                );
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// The path parameters among `parameters`.
+fn of_path(parameters: &Parameters) -> Vec<&Parameter> {
+    parameters
+        .parameters
+        .iter()
+        .filter(|p| matches!(p.ty, Some(ParameterType::Path)))
+        .collect()
+}
+
+fn declared_parameter(path: &str, names: &[&str], p: &Parameter) -> Option<Diagnostic> {
+    let name = &p.name;
+    let d = if !names.contains(&name.as_str()) {
+        Diagnostic::error(format!(
+            "path parameter `{name}` does not appear in `{path}`"
+        ))
+    } else if p.content.requirement == Requirement::Optional {
+        Diagnostic::error(format!("path parameter `{name}` must be required"))
+    } else {
+        return None;
+    };
+
+    Some(d.with_span(&p.span))
+}
@@ -0,0 +1,325 @@
+use crate::analyzer::discriminator::check_variant;
+use crate::analyzer::symbol::{Kind, SymbolTable};
+use crate::analyzer::{SECURITY_KEY, TAG_KEYS};
+use crate::diagnostic::Diagnostic;
+use parser::visit::{
+    walk_object, walk_operation, walk_schema_field, walk_type_with_attributes, ReferenceKind,
+    VisitMut,
+};
+use parser::{
+    Attributes, DiscriminatedUnion, Literal, Object, Operation, SchemaField, Span, Spanned, Type,
+    TypeWithAttributes, Value,
+};
+use std::collections::HashSet;
+
+/// Walks the objects, replacing the identifiers in attribute values with what they name and
+/// checking that the referenced schemas, responses and request bodies are defined.
+pub(super) struct Resolver<'a> {
+    symbols: &'a SymbolTable,
+    /// Type parameters of the generic schema being resolved.
+    type_parameters: Vec<String>,
+    pub used: HashSet<(String, Kind)>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn new(symbols: &'a SymbolTable) -> Self {
+        Resolver {
+            symbols,
+            type_parameters: vec![],
+            used: HashSet::new(),
+            diagnostics: vec![],
+        }
+    }
+
+    pub fn object(&mut self, o: &mut Spanned<Object>) {
+        let first = self.diagnostics.len();
+
+        match &mut o.value {
+            Object::Schema(s) => {
+                self.type_parameters = s.parameters.clone();
+                walk_object(self, &mut o.value);
+                self.type_parameters.clear();
+            }
+            Object::Constant(c) => match self.value(&c.value, &mut vec![c.name.clone()]) {
+                Ok(v) => c.value = v,
+                Err(d) => self.diagnostics.push(d),
+            },
+            Object::SecurityScheme(s) => {
+                if s.is_default {
+                    self.used.insert((s.name.clone(), Kind::SecurityScheme));
+                }
+                walk_object(self, &mut o.value);
+            }
+            _ => walk_object(self, &mut o.value),
+        }
+
+        self.locate(first, &o.span);
+    }
+
+    /// Points the diagnostics from `first` on at `span`, unless they point somewhere already.
+    fn locate(&mut self, first: usize, span: &Span) {
+        for d in &mut self.diagnostics[first..] {
+            *d = d.clone().or_span(span);
+        }
+    }
+
+    fn reference(&mut self, name: &str, kind: Kind) {
+        if self.symbols.get(name, kind).is_some() {
+            self.used.insert((name.to_string(), kind));
+            return;
+        }
+
+        let d = match self.symbols.definitions(name).first() {
+            None => Diagnostic::error(format!("{kind} `{name}` is not defined")),
+            Some(other) => {
+                // used, if wrongly, so it is not reported as unused as well
+                self.used.insert((name.to_string(), other.kind));
+                Diagnostic::error(format!("`{name}` is a {}, not a {kind}", other.kind))
+                    .with_note(format!("`{name}` is defined at {}", other.span))
+            }
+        };
+        self.diagnostics.push(d);
+    }
+
+    /// References the schema `name` given `arguments` type arguments, which must be as many as
+    /// its type parameters.
+    fn schema_reference(&mut self, name: &str, arguments: usize) {
+        self.reference(name, Kind::Schema);
+        let Some(schema) = self.symbols.schema(name) else {
+            return;
+        };
+
+        let parameters = schema.parameters.len();
+        if parameters == arguments {
+            return;
+        }
+        let given = match arguments {
+            0 => "none were".to_string(),
+            1 => "1 was".to_string(),
+            n => format!("{n} were"),
+        };
+        let mut d = Diagnostic::error(format!(
+            "schema `{name}` takes {parameters} type argument{}, but {given} given",
+            if parameters == 1 { "" } else { "s" }
+        ));
+        if let Some(definition) = self.symbols.get(name, Kind::Schema) {
+            d = d.with_note(match parameters {
+                0 => format!("`{name}` is defined at {}", definition.span),
+                _ => format!(
+                    "`{name}<{}>` is defined at {}",
+                    schema.parameters.join(", "),
+                    definition.span
+                ),
+            });
+        }
+        self.diagnostics.push(d);
+    }
+
+    fn discriminated(&mut self, union: &DiscriminatedUnion) {
+        let mut values = HashSet::new();
+        for (value, schema) in &union.mapping {
+            if !values.insert(value) {
+                self.diagnostics.push(Diagnostic::error(format!(
+                    "discriminator value `{value}` is mapped more than once"
+                )));
+            }
+            if self.type_parameters.contains(schema) {
+                self.diagnostics.push(
+                    Diagnostic::error(format!(
+                        "type parameter `{schema}` cannot be a variant of a discriminated union"
+                    ))
+                    .with_note("the variants must be schemas with the discriminator property"),
+                );
+                continue;
+            }
+            self.schema_reference(schema, 0);
+            if let Some(d) = check_variant(self.symbols, schema, &union.property) {
+                self.diagnostics.push(d);
+            }
+        }
+    }
+
+    /// Names under `tag` and `tags` stand for tags even when a schema has the same name.
+    fn tag_value(&mut self, v: &Value) -> Result<Value, Diagnostic> {
+        match v {
+            Value::Identifier(name) => {
+                self.reference(name, Kind::Tag);
+                Ok(Value::Immediate(Literal::String(name.clone())))
+            }
+            Value::Immediate(Literal::String(_)) => Ok(v.clone()),
+            Value::List(values) => Ok(Value::List(
+                values
+                    .iter()
+                    .map(|v| self.tag_value(v))
+                    .collect::<Result<_, _>>()?,
+            )),
+            _ => Err(Diagnostic::error(
+                "a tag must be given by its name or a list of names",
+            )),
+        }
+    }
+
+    /// Security requirements are written as a list of names, or of objects mapping names to
+    /// the scopes they need. An empty list means no security.
+    fn security_value(&mut self, v: &Value) -> Result<Value, Diagnostic> {
+        let Value::List(items) = v else {
+            return Err(Diagnostic::error(
+                "security requirements must be given by a list",
+            ));
+        };
+
+        let mut requirements = vec![];
+        for item in items {
+            match item {
+                Value::Identifier(name) => {
+                    self.reference(name, Kind::SecurityScheme);
+                    requirements.push(Value::Object(vec![(name.clone(), Value::List(vec![]))]));
+                }
+                Value::Object(entries) => {
+                    for (name, _) in entries {
+                        self.reference(name, Kind::SecurityScheme);
+                    }
+                    requirements.push(self.value(item, &mut vec![])?);
+                }
+                _ => {
+                    return Err(Diagnostic::error(
+                        "a security requirement must be the name of a security scheme or an object mapping names to scopes",
+                    ))
+                }
+            }
+        }
+
+        Ok(Value::List(requirements))
+    }
+
+    /// `visiting` holds the constants and servers being resolved, to report those defined by
+    /// themselves.
+    fn value(&mut self, v: &Value, visiting: &mut Vec<String>) -> Result<Value, Diagnostic> {
+        Ok(match v {
+            Value::Immediate(_) => v.clone(),
+            Value::Identifier(name) => self.identifier(name, visiting)?,
+            Value::List(values) => Value::List(
+                values
+                    .iter()
+                    .map(|v| self.value(v, visiting))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Value::Object(entries) => Value::Object(
+                entries
+                    .iter()
+                    .map(|(key, v)| Ok((key.clone(), self.value(v, visiting)?)))
+                    .collect::<Result<_, Diagnostic>>()?,
+            ),
+        })
+    }
+
+    /// A tag stands for its name, a schema, response or request body for a `$ref` to it, a
+    /// constant for its value, a server for the server object and a security scheme for a
+    /// requirement of it without scopes.
+    fn identifier(&mut self, name: &str, visiting: &mut Vec<String>) -> Result<Value, Diagnostic> {
+        let reference = |components: &str| {
+            Value::Object(vec![(
+                "$ref".to_string(),
+                Value::Immediate(Literal::String(format!("#/components/{components}/{name}"))),
+            )])
+        };
+
+        let kind = self
+            .symbols
+            .preferred_kind(name)
+            .ok_or_else(|| Diagnostic::error(format!("`{name}` is not defined")))?;
+        self.used.insert((name.to_string(), kind));
+
+        match kind {
+            Kind::Tag => Ok(Value::Immediate(Literal::String(name.to_string()))),
+            Kind::Schema => Ok(reference("schemas")),
+            Kind::Response => Ok(reference("responses")),
+            Kind::RequestBody => Ok(reference("requestBodies")),
+            Kind::SecurityScheme => {
+                Ok(Value::Object(vec![(name.to_string(), Value::List(vec![]))]))
+            }
+            Kind::Constant | Kind::Server => {
+                if visiting.iter().any(|n| n == name) {
+                    visiting.push(name.to_string());
+                    return Err(
+                        Diagnostic::error(format!("{kind} `{name}` is defined by itself"))
+                            .with_note(format!("`{}`", visiting.join("` -> `"))),
+                    );
+                }
+                let v = match kind {
+                    Kind::Constant => self.symbols.constant(name),
+                    _ => self.symbols.server(name),
+                }
+                .expect("constants and servers are in the symbol table")
+                .clone();
+                visiting.push(name.to_string());
+                let v = self.value(&v, visiting)?;
+                visiting.pop();
+                Ok(v)
+            }
+        }
+    }
+}
+
+impl VisitMut for Resolver<'_> {
+    fn visit_operation(&mut self, op: &mut Operation) {
+        let first = self.diagnostics.len();
+        walk_operation(self, op);
+        self.locate(first, &op.span);
+    }
+
+    fn visit_schema_field(&mut self, field: &mut SchemaField) {
+        let first = self.diagnostics.len();
+        walk_schema_field(self, field);
+        self.locate(first, &field.span);
+    }
+
+    fn visit_type_with_attributes(&mut self, twa: &mut TypeWithAttributes) {
+        match &mut twa.target_type {
+            Type::Instance { name, arguments } => {
+                self.visit_attributes(&mut twa.attributes);
+                self.schema_reference(name, arguments.len());
+                arguments
+                    .iter_mut()
+                    .for_each(|ty| self.visit_type_with_attributes(ty));
+            }
+            Type::Discriminated(union) => {
+                self.visit_attributes(&mut twa.attributes);
+                self.discriminated(union);
+            }
+            _ => walk_type_with_attributes(self, twa),
+        }
+    }
+
+    fn visit_attributes(&mut self, attrs: &mut Attributes) {
+        let keys = attrs.keys().cloned().collect::<Vec<_>>();
+        for key in keys {
+            let v = &attrs[&key];
+            let resolved = if TAG_KEYS.contains(&key.as_str()) {
+                self.tag_value(v)
+            } else if key == SECURITY_KEY {
+                self.security_value(v)
+            } else {
+                self.value(v, &mut vec![])
+            };
+            match resolved {
+                Ok(v) => {
+                    attrs.insert(key, v);
+                }
+                Err(d) => self.diagnostics.push(d),
+            }
+        }
+    }
+
+    fn visit_reference(&mut self, name: &mut String, kind: ReferenceKind) {
+        match kind {
+            ReferenceKind::Schema => self.schema_reference(name, 0),
+            ReferenceKind::Response => self.reference(name, Kind::Response),
+            ReferenceKind::RequestBody => self.reference(name, Kind::RequestBody),
+            ReferenceKind::Server => self.reference(name, Kind::Server),
+            // attribute and constant values are resolved as a whole
+            ReferenceKind::Value => {}
+        }
+    }
+}
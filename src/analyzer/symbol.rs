@@ -0,0 +1,193 @@
+use indexmap::IndexMap;
+use parser::{
+    Attributes, Literal, Object, Schema, SchemaContent, Server, Span, Spanned, Type,
+    TypeWithAttributes, Value,
+};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// Kind of a name defined at the top level of the sources. Each kind has its own
+/// namespace, so a tag may share its name with a schema.
+///
+/// When a name in an attribute value could stand for several kinds, the one declared
+/// first here wins.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub(crate) enum Kind {
+    Constant,
+    Schema,
+    Response,
+    RequestBody,
+    Tag,
+    Server,
+    SecurityScheme,
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Kind::Constant => "constant",
+            Kind::Schema => "schema",
+            Kind::Response => "response",
+            Kind::RequestBody => "request body",
+            Kind::Tag => "tag",
+            Kind::Server => "server",
+            Kind::SecurityScheme => "security scheme",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Definition {
+    pub kind: Kind,
+    pub span: Span,
+}
+
+/// Names defined by the objects of all the source files, in the order of definition.
+pub(crate) struct SymbolTable {
+    definitions: HashMap<String, Vec<Definition>>,
+    constants: HashMap<String, Value>,
+    servers: HashMap<String, Value>,
+    schemas: HashMap<String, Schema>,
+}
+
+impl SymbolTable {
+    pub fn new(objects: &[Spanned<Object>]) -> Self {
+        let mut definitions = HashMap::<String, Vec<Definition>>::new();
+        let mut constants = HashMap::new();
+        let mut servers = HashMap::new();
+        let mut schemas = HashMap::new();
+
+        for o in objects {
+            let Some((name, kind)) = defined_name(&o.value) else {
+                continue;
+            };
+            if let Object::Constant(c) = &o.value {
+                constants
+                    .entry(c.name.clone())
+                    .or_insert_with(|| c.value.clone());
+            }
+            if let Object::Server(s) = &o.value {
+                servers
+                    .entry(s.name.clone())
+                    .or_insert_with(|| server_value(s));
+            }
+            if let Some(schema) = defined_schema(&o.value) {
+                schemas.entry(name.to_string()).or_insert(schema);
+            }
+            definitions
+                .entry(name.to_string())
+                .or_default()
+                .push(Definition {
+                    kind,
+                    span: o.span.clone(),
+                });
+        }
+
+        SymbolTable {
+            definitions,
+            constants,
+            servers,
+            schemas,
+        }
+    }
+
+    pub fn definitions(&self, name: &str) -> &[Definition] {
+        self.definitions
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The first definition of `name` as `kind`.
+    pub fn get(&self, name: &str, kind: Kind) -> Option<&Definition> {
+        self.definitions(name).iter().find(|d| d.kind == kind)
+    }
+
+    /// The kind `name` stands for in an attribute value.
+    pub fn preferred_kind(&self, name: &str) -> Option<Kind> {
+        self.definitions(name).iter().map(|d| d.kind).min()
+    }
+
+    pub fn constant(&self, name: &str) -> Option<&Value> {
+        self.constants.get(name)
+    }
+
+    /// The server object `name` stands for in an attribute value.
+    pub fn server(&self, name: &str) -> Option<&Value> {
+        self.servers.get(name)
+    }
+
+    /// The schema or enum `name`, as written before it is resolved.
+    pub fn schema(&self, name: &str) -> Option<&Schema> {
+        self.schemas.get(name)
+    }
+}
+
+/// The schema `object` defines, where an enum is a schema defined by its enum type.
+pub(crate) fn defined_schema(object: &Object) -> Option<Schema> {
+    match object {
+        Object::Schema(s) => Some(s.clone()),
+        Object::Enum(e) => Some(Schema {
+            name: Some(e.name.clone()),
+            doc: e.doc.clone(),
+            parameters: vec![],
+            base: None,
+            content: SchemaContent::Typedef(Box::new(TypeWithAttributes {
+                target_type: Type::Enum(e.content.clone()),
+                attributes: IndexMap::new(),
+            })),
+        }),
+        _ => None,
+    }
+}
+
+/// The server as written in the `servers` of the output.
+fn server_value(server: &Server) -> Value {
+    let url = Value::Immediate(Literal::String(server.url.clone()));
+    Value::Object(
+        [("url".to_string(), url)]
+            .into_iter()
+            .chain(server_attributes(server))
+            .collect(),
+    )
+}
+
+/// Fields of the server object of `server` besides its URL.
+pub(crate) fn server_attributes(server: &Server) -> Attributes {
+    let string = |s: &str| Value::Immediate(Literal::String(s.to_string()));
+
+    let mut attributes = Attributes::new();
+    if let Some(description) = &server.description {
+        attributes.insert("description".to_string(), string(description));
+    }
+    if !server.variables.is_empty() {
+        let variables = server
+            .variables
+            .iter()
+            .map(|(name, v)| {
+                let mut variable = vec![("default".to_string(), string(&v.default))];
+                variable.extend(v.attributes.clone());
+                (name.clone(), Value::Object(variable))
+            })
+            .collect();
+        attributes.insert("variables".to_string(), Value::Object(variables));
+    }
+    attributes.extend(server.attributes.clone());
+
+    attributes
+}
+
+/// Name and kind of the symbol `object` defines, if any.
+pub(crate) fn defined_name(object: &Object) -> Option<(&str, Kind)> {
+    match object {
+        Object::Constant(c) => Some((&c.name, Kind::Constant)),
+        Object::Schema(s) => s.name.as_deref().map(|name| (name, Kind::Schema)),
+        Object::Enum(e) => Some((&e.name, Kind::Schema)),
+        Object::Response(r) => Some((&r.name, Kind::Response)),
+        Object::RequestBody(r) => Some((&r.name, Kind::RequestBody)),
+        Object::Tag(t) => Some((&t.name, Kind::Tag)),
+        Object::Server(s) => Some((&s.name, Kind::Server)),
+        Object::SecurityScheme(s) => Some((&s.name, Kind::SecurityScheme)),
+        Object::Path(_) | Object::Info(_) => None,
+    }
+}
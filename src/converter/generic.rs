@@ -0,0 +1,272 @@
+use crate::diagnostic::Diagnostic;
+use indexmap::IndexMap;
+use parser::visit::{walk_type_with_attributes, VisitMut};
+use parser::{Object, ReferenceOr, Schema, SchemaContent, Spanned, Type, TypeWithAttributes};
+use std::collections::{HashMap, HashSet};
+
+/// How the instances of generic schemas are named in the output.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum GenericNaming {
+    /// `PageUser` for `Page<User>`, and `ResultUserError` for `Result<User, Error>`.
+    #[default]
+    Concat,
+    /// `Page_User` for `Page<User>`, and `Result_User_Error` for `Result<User, Error>`.
+    Underscore,
+    /// `PageOfUser` for `Page<User>`, and `ResultOfUserAndError` for `Result<User, Error>`.
+    Of,
+}
+
+impl GenericNaming {
+    fn name(self, schema: &str, arguments: &[String]) -> String {
+        match self {
+            GenericNaming::Concat => format!("{schema}{}", arguments.concat()),
+            GenericNaming::Underscore => format!("{schema}_{}", arguments.join("_")),
+            GenericNaming::Of => format!("{schema}Of{}", arguments.join("And")),
+        }
+    }
+}
+
+/// Nesting of instances beyond which a generic schema is taken to instantiate itself with ever
+/// longer type arguments.
+const MAX_DEPTH: usize = 32;
+
+/// Replaces every instance of a generic schema, like `Page<User>`, with a reference to a schema
+/// named by `naming` that has the type arguments substituted for the type parameters.
+///
+/// The instances are defined in place of their generic schema, in the order they are first
+/// used, and the generic schemas themselves are left out.
+pub(super) fn monomorphize(
+    objects: Vec<Spanned<Object>>,
+    naming: GenericNaming,
+) -> Result<Vec<Spanned<Object>>, Diagnostic> {
+    let mut m = Monomorphizer {
+        naming,
+        generics: HashMap::new(),
+        defined: HashSet::new(),
+        instantiated: HashMap::new(),
+        instances: IndexMap::new(),
+        depth: 0,
+        error: None,
+    };
+    for o in &objects {
+        match &o.value {
+            Object::Schema(s) if !s.parameters.is_empty() => {
+                let name = s.name.clone().unwrap_or_default();
+                m.generics.entry(name).or_insert_with(|| Spanned {
+                    value: s.clone(),
+                    span: o.span.clone(),
+                });
+            }
+            Object::Schema(Schema {
+                name: Some(name), ..
+            }) => {
+                m.defined.insert(name.clone());
+            }
+            Object::Enum(e) => {
+                m.defined.insert(e.name.clone());
+            }
+            _ => {}
+        }
+    }
+
+    let mut lowered = Vec::with_capacity(objects.len());
+    for mut o in objects {
+        if generic_name(&o.value).is_none() {
+            m.visit_object(&mut o.value);
+            if let Some(d) = m.error.take() {
+                return Err(d.or_span(&o.span));
+            }
+        }
+        lowered.push(o);
+    }
+
+    Ok(lowered
+        .into_iter()
+        .flat_map(|o| match generic_name(&o.value) {
+            Some(name) => m.instances.shift_remove(name).unwrap_or_default(),
+            None => vec![o],
+        })
+        .collect())
+}
+
+fn generic_name(object: &Object) -> Option<&str> {
+    match object {
+        Object::Schema(s) if !s.parameters.is_empty() => s.name.as_deref(),
+        _ => None,
+    }
+}
+
+struct Monomorphizer {
+    naming: GenericNaming,
+    generics: HashMap<String, Spanned<Schema>>,
+    /// Names of the schemas and enums, which the instances must not take.
+    defined: HashSet<String>,
+    /// Instances defined so far, and the generic schema and type arguments each is named for.
+    instantiated: HashMap<String, String>,
+    /// Instances of each generic schema, in the order they are first used.
+    instances: IndexMap<String, Vec<Spanned<Object>>>,
+    depth: usize,
+    /// First error found, after which the walk stops instantiating.
+    error: Option<Diagnostic>,
+}
+
+impl VisitMut for Monomorphizer {
+    fn visit_type_with_attributes(&mut self, twa: &mut TypeWithAttributes) {
+        if self.error.is_some() {
+            return;
+        }
+        walk_type_with_attributes(self, twa);
+
+        if let Type::Instance { name, arguments } = &twa.target_type {
+            if self.error.is_some() {
+                return;
+            }
+            match self.instantiate(name, arguments) {
+                Ok(instance) => twa.target_type = Type::Schema(ReferenceOr::Ref(instance)),
+                Err(d) => self.error = Some(d),
+            }
+        }
+    }
+}
+
+impl Monomorphizer {
+    /// Name of the instance of the generic schema `name`, which is defined when it is first
+    /// used.
+    fn instantiate(
+        &mut self,
+        name: &str,
+        arguments: &[TypeWithAttributes],
+    ) -> Result<String, Diagnostic> {
+        let generic = self
+            .generics
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Diagnostic::error(format!("schema `{name}` is not generic")))?;
+        let argument_names = arguments
+            .iter()
+            .map(|a| self.argument_name(a))
+            .collect::<Result<Vec<_>, _>>()?;
+        let instance = self.naming.name(name, &argument_names);
+        let written = format!("{name}<{}>", argument_names.join(", "));
+
+        let conflict = match self.instantiated.get(&instance) {
+            Some(other) if *other == written => return Ok(instance),
+            Some(other) => Some(format!(
+                "`{written}` and `{other}` are both named `{instance}`"
+            )),
+            None if self.defined.contains(&instance) => Some(format!(
+                "`{written}` is named `{instance}`, which is already defined"
+            )),
+            None => None,
+        };
+        if let Some(conflict) = conflict {
+            return Err(Diagnostic::error(conflict)
+                .with_note("rename the schema, or name the instances with `--generic-naming`"));
+        }
+        if self.depth == MAX_DEPTH {
+            return Err(
+                Diagnostic::error(format!("instantiating `{name}` never ends"))
+                    .with_span(&generic.span)
+                    .with_note(format!(
+                        "each instance of `{name}` uses one with longer type arguments"
+                    )),
+            );
+        }
+        self.instantiated.insert(instance.clone(), written);
+
+        let mut content = generic.value.content.clone();
+        Substitution {
+            parameters: &generic.value.parameters,
+            arguments,
+        }
+        .visit_schema_content(&mut content);
+        let slot = self.instances.entry(name.to_string()).or_default();
+        let index = slot.len();
+        slot.push(instance_object(&generic, &instance, content.clone()));
+
+        self.depth += 1;
+        self.visit_schema_content(&mut content);
+        self.depth -= 1;
+        if let Some(d) = self.error.take() {
+            return Err(d.or_span(&generic.span));
+        }
+        self.instances[name][index] = instance_object(&generic, &instance, content);
+
+        Ok(instance)
+    }
+
+    /// Name of a type argument in the names of instances, such as `User` or `ListInt64`.
+    fn argument_name(&self, argument: &TypeWithAttributes) -> Result<String, Diagnostic> {
+        if !argument.attributes.is_empty() {
+            return Err(Diagnostic::error("type arguments cannot have attributes")
+                .with_note("define a schema with the attributes and pass it instead"));
+        }
+
+        Ok(match &argument.target_type {
+            Type::Schema(ReferenceOr::Ref(name)) => name.split('.').map(capitalize).collect(),
+            Type::Integer { format: None } => "Int".to_string(),
+            Type::Number { format: None } => "Number".to_string(),
+            Type::String { format: None } => "String".to_string(),
+            Type::Integer {
+                format: Some(format),
+            }
+            | Type::Number {
+                format: Some(format),
+            }
+            | Type::String {
+                format: Some(format),
+            } => format.split('-').map(capitalize).collect(),
+            Type::Bool => "Bool".to_string(),
+            Type::Object => "Object".to_string(),
+            Type::List { item_type } => self.naming.name("List", &[self.argument_name(item_type)?]),
+            _ => {
+                return Err(Diagnostic::error(
+                    "type arguments must be named schemas, lists or built-in types",
+                ))
+            }
+        })
+    }
+}
+
+fn instance_object(
+    generic: &Spanned<Schema>,
+    instance: &str,
+    content: SchemaContent,
+) -> Spanned<Object> {
+    Spanned {
+        value: Object::Schema(Schema {
+            name: Some(instance.to_string()),
+            parameters: vec![],
+            content,
+            ..generic.value.clone()
+        }),
+        span: generic.span.clone(),
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+    }
+}
+
+/// Replaces the type parameters with the type arguments at the same positions.
+struct Substitution<'a> {
+    parameters: &'a [String],
+    arguments: &'a [TypeWithAttributes],
+}
+
+impl VisitMut for Substitution<'_> {
+    fn visit_type_with_attributes(&mut self, twa: &mut TypeWithAttributes) {
+        match &twa.target_type {
+            Type::Parameter(p) => {
+                if let Some(i) = self.parameters.iter().position(|q| q == p) {
+                    twa.target_type = self.arguments[i].target_type.clone();
+                }
+            }
+            _ => walk_type_with_attributes(self, twa),
+        }
+    }
+}
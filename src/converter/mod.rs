@@ -1,24 +1,28 @@
 use crate::converter::object::type_with_attributes;
+use crate::diagnostic::Diagnostic;
 use crate::openapi::MediaType;
+use indexmap::IndexMap;
 use parser::TypeWithAttributes;
-use std::collections::HashMap;
 
+mod generic;
 mod object;
 mod path;
 mod request_body;
 mod response;
 mod schema;
 
+pub use generic::GenericNaming;
 pub(crate) use object::generate;
+pub use schema::Inheritance;
 
-fn media_content(twa: &TypeWithAttributes) -> HashMap<String, MediaType> {
-    let mut hm = HashMap::new();
-    hm.insert("application/json".to_string(), media_type(&twa));
-    hm
+fn media_content(twa: &TypeWithAttributes) -> Result<IndexMap<String, MediaType>, Diagnostic> {
+    let mut hm = IndexMap::new();
+    hm.insert("application/json".to_string(), media_type(twa)?);
+    Ok(hm)
 }
 
-fn media_type(twa: &TypeWithAttributes) -> MediaType {
-    MediaType {
-        schema: type_with_attributes(twa),
-    }
+fn media_type(twa: &TypeWithAttributes) -> Result<MediaType, Diagnostic> {
+    Ok(MediaType {
+        schema: type_with_attributes(twa)?,
+    })
 }
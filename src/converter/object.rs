@@ -1,71 +1,137 @@
+use crate::analyzer::{defined_schema, server_attributes};
+use crate::converter::generic::{monomorphize, GenericNaming};
 use crate::converter::path::path_values;
 use crate::converter::request_body::request_body;
 use crate::converter::response::response;
-use crate::converter::schema::{enum_content, schema};
+use crate::converter::schema::{
+    discriminated, documented, enum_content, extends_schema, schema, Inheritance,
+};
+use crate::diagnostic::Diagnostic;
 use crate::openapi::{Components, Info, OpenApi};
-use parser::{Attributes, Literal, Object, Path, Tag, Type, TypeWithAttributes, Value};
+use indexmap::IndexMap;
+use parser::{
+    Attributes, Literal, Object, Path, SecuritySchemeType, Spanned, Tag, Type, TypeWithAttributes,
+    Value,
+};
 use serde::Serialize;
 use std::collections::HashMap;
 use std::fmt::Debug;
 
+type CollectedObjects = (
+    Components,
+    Vec<crate::openapi::Tag>,
+    Vec<Spanned<Path>>,
+    InfoConfig,
+    IndexMap<String, crate::openapi::Server>,
+);
+
 struct InfoConfig {
-    info: HashMap<String, parser::Info>,
+    info: HashMap<String, Spanned<parser::Info>>,
     default: Option<String>,
 }
 
-pub(crate) fn generate(objects: Vec<Object>, config: Option<String>) -> OpenApi {
-    let (components, tags, paths, info) = components(&objects);
+pub(crate) fn generate(
+    objects: Vec<Spanned<Object>>,
+    config: Option<String>,
+    inheritance: Inheritance,
+    naming: GenericNaming,
+) -> Result<OpenApi, Diagnostic> {
+    let objects = monomorphize(objects, naming)?;
+    let (components, tags, paths, info, servers) = components(&objects, inheritance)?;
 
-    let paths = path_values(paths);
+    let paths = path_values(paths, &tags)?;
+    let (info, selected_servers) = resolve_info(&info, config)?;
+    let security = default_security(&objects);
 
-    OpenApi {
+    Ok(OpenApi {
         openapi: "3.1.0".to_string(),
-        info: resolve_info(&info, config),
-        servers: vec![],
+        info,
+        servers: select_servers(servers, selected_servers)?,
         paths,
         components,
+        security,
         tags,
-    }
+    })
 }
 
 fn components(
-    objects: &Vec<Object>,
-) -> (Components, Vec<crate::openapi::Tag>, Vec<Path>, InfoConfig) {
+    objects: &Vec<Spanned<Object>>,
+    inheritance: Inheritance,
+) -> Result<CollectedObjects, Diagnostic> {
     let mut cs = Components::default();
     let mut tags = Vec::new();
     let mut paths = Vec::new();
+    let mut servers = IndexMap::new();
 
     let mut info = HashMap::new();
     let mut default_info = None;
 
+    let mut schemas = HashMap::new();
     for o in objects {
-        match o {
+        if let Some(s) = defined_schema(&o.value) {
+            schemas
+                .entry(s.name.clone().unwrap_or_default())
+                .or_insert(s);
+        }
+    }
+
+    for o in objects {
+        let span = &o.span;
+        match &o.value {
             Object::Schema(s) => {
-                cs.schemas.insert(s.name.clone().unwrap(), schema(s));
+                let s = extends_schema(&schemas, s, inheritance).map_err(|d| d.or_span(span))?;
+                cs.schemas.insert(
+                    s.name.clone().unwrap_or_default(),
+                    schema(&s).map_err(|d| d.or_span(span))?,
+                );
             }
             Object::Tag(t) => tags.push(tag(t)),
             Object::Response(r) => {
-                cs.responses.insert(r.name.clone(), response(r));
+                cs.responses
+                    .insert(r.name.clone(), response(r).map_err(|d| d.or_span(span))?);
             }
             Object::RequestBody(r) => {
-                cs.request_bodies.insert(r.name.clone(), request_body(r));
+                cs.request_bodies.insert(
+                    r.name.clone(),
+                    request_body(r).map_err(|d| d.or_span(span))?,
+                );
             }
             Object::Enum(e) => {
-                cs.schemas.insert(e.name.clone(), enum_content(&e.content));
+                cs.schemas
+                    .insert(e.name.clone(), documented(enum_content(&e.content), &e.doc));
             }
             Object::Path(p) => {
-                paths.push(p.clone());
+                paths.push(Spanned {
+                    span: span.clone(),
+                    value: p.as_ref().clone(),
+                });
+            }
+            Object::Constant(_) => {}
+            Object::Server(s) => {
+                servers.insert(s.name.clone(), server(s));
+            }
+            Object::SecurityScheme(s) => {
+                cs.security_schemes.insert(
+                    s.name.clone(),
+                    security_scheme(s).map_err(|d| d.or_span(span))?,
+                );
             }
             Object::Info(i) => {
                 if i.is_default {
                     default_info = Some(i.config_name.to_string());
                 }
-                info.insert(i.config_name.to_string(), i.clone());
+                info.insert(
+                    i.config_name.to_string(),
+                    Spanned {
+                        span: span.clone(),
+                        value: i.clone(),
+                    },
+                );
             }
         }
     }
 
-    (
+    Ok((
         cs,
         tags,
         paths,
@@ -73,7 +139,84 @@ fn components(
             info,
             default: default_info,
         },
-    )
+        servers,
+    ))
+}
+
+fn server(server: &parser::Server) -> crate::openapi::Server {
+    crate::openapi::Server {
+        url: server.url.clone(),
+        attributes: attributes(&server_attributes(server)),
+    }
+}
+
+fn security_scheme(
+    scheme: &parser::SecurityScheme,
+) -> Result<crate::openapi::SecurityScheme, Diagnostic> {
+    let required: &[&str] = match scheme.scheme_type {
+        SecuritySchemeType::Http => &["scheme"],
+        SecuritySchemeType::ApiKey => &["name", "in"],
+        SecuritySchemeType::OAuth2 => &["flows"],
+        SecuritySchemeType::OpenIdConnect => &["openIdConnectUrl"],
+    };
+    if let Some(missing) = required.iter().find(|f| !scheme.fields.contains_key(**f)) {
+        return Err(Diagnostic::error(format!(
+            "security scheme `{}` does not set `{missing}`",
+            scheme.name
+        ))
+        .with_note(format!(
+            "`{}` schemes need {}",
+            scheme.scheme_type,
+            required
+                .iter()
+                .map(|f| format!("`{f}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+
+    let mut attrs = crate::openapi::Attributes::new();
+    if let Some(doc) = &scheme.doc {
+        attrs.insert("description".to_string(), doc.as_str().into());
+    }
+    attrs.extend(attributes(&scheme.fields));
+
+    Ok(crate::openapi::SecurityScheme {
+        scheme_type: scheme.scheme_type.to_string(),
+        attributes: attrs,
+    })
+}
+
+/// Requirements of the `default security` schemes, any one of which is enough.
+fn default_security(objects: &[Spanned<Object>]) -> Vec<crate::openapi::SecurityRequirement> {
+    objects
+        .iter()
+        .filter_map(|o| match &o.value {
+            Object::SecurityScheme(s) if s.is_default => {
+                Some(IndexMap::from([(s.name.clone(), vec![])]))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The servers named by the info config in its order, or all of them in the order of
+/// definition when it names none.
+fn select_servers(
+    mut servers: IndexMap<String, crate::openapi::Server>,
+    selected: Option<Vec<String>>,
+) -> Result<Vec<crate::openapi::Server>, Diagnostic> {
+    match selected {
+        None => Ok(servers.into_values().collect()),
+        Some(names) => names
+            .into_iter()
+            .map(|name| {
+                servers
+                    .shift_remove(&name)
+                    .ok_or_else(|| Diagnostic::error(format!("server `{name}` is not defined")))
+            })
+            .collect(),
+    }
 }
 
 fn tag(tag: &Tag) -> crate::openapi::Tag {
@@ -83,26 +226,75 @@ fn tag(tag: &Tag) -> crate::openapi::Tag {
     }
 }
 
-fn resolve_info(info_config: &InfoConfig, config: Option<String>) -> Info {
-    let config = config.or(info_config.default.clone()).unwrap();
-    let using = info_config.info.get(&config).unwrap();
-    let info = extends_info(&info_config, using.clone());
+/// The selected info config, merged with the configs it extends, and the servers it names.
+fn resolve_info(
+    info_config: &InfoConfig,
+    config: Option<String>,
+) -> Result<(Info, Option<Vec<String>>), Diagnostic> {
+    let config = config.or(info_config.default.clone()).ok_or_else(|| {
+        Diagnostic::error("no info config is selected")
+            .with_note("mark one config as `default info` or pass `--config <NAME>`")
+    })?;
+    let using = info_config.info.get(&config).ok_or_else(|| {
+        let mut known = info_config.info.keys().cloned().collect::<Vec<_>>();
+        known.sort();
+        Diagnostic::error(format!("info config `{config}` is not defined"))
+            .with_note(format!("defined configs: {}", known.join(", ")))
+    })?;
+    let info = extends_info(info_config, using, &mut vec![])?;
 
-    Info {
-        title: info.title.unwrap(),
-        version: info.version.unwrap(),
-        description: info.description,
-        terms_of_service: info.terms_of_service,
-    }
+    Ok((
+        Info {
+            title: info
+                .title
+                .ok_or_else(|| missing_info_field(using, "title"))?,
+            summary: info.summary,
+            version: info
+                .version
+                .ok_or_else(|| missing_info_field(using, "version"))?,
+            description: info.description,
+            terms_of_service: info.terms_of_service,
+        },
+        info.servers,
+    ))
+}
+
+fn missing_info_field(info: &Spanned<parser::Info>, field: &str) -> Diagnostic {
+    Diagnostic::error(format!(
+        "info config `{}` does not set `{field}`",
+        info.value.config_name
+    ))
+    .with_span(&info.span)
+    .with_note(format!(
+        "add `{field} = \"...\"` to it or to one of the configs it extends"
+    ))
 }
 
-fn extends_info(info_config: &InfoConfig, info: parser::Info) -> parser::Info {
-    match info.base {
-        None => info,
+fn extends_info(
+    info_config: &InfoConfig,
+    info: &Spanned<parser::Info>,
+    visiting: &mut Vec<String>,
+) -> Result<parser::Info, Diagnostic> {
+    if visiting.contains(&info.value.config_name) {
+        return Err(Diagnostic::error(format!(
+            "info config `{}` extends itself",
+            info.value.config_name
+        ))
+        .with_span(&info.span)
+        .with_note(format!("extends chain: {}", visiting.join(" -> "))));
+    }
+    visiting.push(info.value.config_name.clone());
+
+    match info.value.base.clone() {
+        None => Ok(info.value.clone()),
         Some(base) => {
-            let base = info_config.info.get(&base).unwrap().clone();
-            let base = extends_info(&info_config, base);
-            parser::Info {
+            let base = info_config.info.get(&base).ok_or_else(|| {
+                Diagnostic::error(format!("base info config `{base}` is not defined"))
+                    .with_span(&info.span)
+            })?;
+            let base = extends_info(info_config, base, visiting)?;
+            let info = info.value.clone();
+            Ok(parser::Info {
                 is_default: true,
                 config_name: "".to_string(),
                 title: info.title.or(base.title),
@@ -110,20 +302,27 @@ fn extends_info(info_config: &InfoConfig, info: parser::Info) -> parser::Info {
                 description: info.description.or(base.description),
                 terms_of_service: info.terms_of_service.or(base.terms_of_service),
                 summary: info.summary.or(base.summary),
+                servers: info.servers.or(base.servers),
                 base: None,
-            }
+            })
         }
     }
 }
 
 pub(super) fn type_with_attributes(
     twa: &TypeWithAttributes,
-) -> crate::openapi::ReferenceOr<crate::openapi::Schema> {
-    match &twa.target_type {
+) -> Result<crate::openapi::ReferenceOr<crate::openapi::Schema>, Diagnostic> {
+    Ok(match &twa.target_type {
         Type::Integer { format } => {
             crate::openapi::ReferenceOr::Value(crate::openapi::Schema::Integer {
                 format: format.clone(),
-                attributes: attributes(&twa.attributes),
+                attributes: numeric_attributes(&twa.attributes)?,
+            })
+        }
+        Type::Number { format } => {
+            crate::openapi::ReferenceOr::Value(crate::openapi::Schema::Number {
+                format: format.clone(),
+                attributes: numeric_attributes(&twa.attributes)?,
             })
         }
         Type::String { format } => {
@@ -133,26 +332,81 @@ pub(super) fn type_with_attributes(
                 selection: None,
             })
         }
-        Type::Float => {
-            todo!()
-        }
         Type::Bool => crate::openapi::ReferenceOr::Value(crate::openapi::Schema::Boolean {
             attributes: attributes(&twa.attributes),
         }),
         Type::List { item_type } => {
             crate::openapi::ReferenceOr::Value(crate::openapi::Schema::Array {
-                items: Box::new(type_with_attributes(&item_type)),
+                items: Box::new(type_with_attributes(item_type)?),
                 attributes: attributes(&twa.attributes),
             })
         }
         Type::Object => crate::openapi::ReferenceOr::Value(crate::openapi::Schema::Object {
             required: vec![],
-            properties: HashMap::new(),
+            properties: IndexMap::new(),
             attributes: attributes(&twa.attributes),
         }),
-        Type::Schema(r) => r.to_reference_or(),
+        Type::Schema(r) => r.to_reference_or()?,
         Type::Enum(e) => enum_content(e),
+        Type::OneOf(types) => crate::openapi::ReferenceOr::Value(crate::openapi::Schema::OneOf {
+            one_of: composed_types(types)?,
+            discriminator: None,
+            attributes: attributes(&twa.attributes),
+        }),
+        Type::AnyOf(types) => crate::openapi::ReferenceOr::Value(crate::openapi::Schema::AnyOf {
+            any_of: composed_types(types)?,
+            attributes: attributes(&twa.attributes),
+        }),
+        Type::AllOf(types) => crate::openapi::ReferenceOr::Value(crate::openapi::Schema::AllOf {
+            all_of: composed_types(types)?,
+            attributes: attributes(&twa.attributes),
+        }),
+        Type::Discriminated(union) => discriminated(union, &twa.attributes),
+        // replaced by the instances before conversion
+        Type::Instance { name, .. } => {
+            return Err(Diagnostic::error(format!(
+                "generic schema `{name}` is not instantiated"
+            )))
+        }
+        Type::Parameter(name) => {
+            return Err(Diagnostic::error(format!(
+                "type parameter `{name}` is used outside its generic schema"
+            )))
+        }
+    })
+}
+
+fn composed_types(
+    types: &[TypeWithAttributes],
+) -> Result<Vec<crate::openapi::ReferenceOr<crate::openapi::Schema>>, Diagnostic> {
+    types.iter().map(type_with_attributes).collect()
+}
+
+/// Attributes of numeric schemas, where the range and step must be given as numbers.
+fn numeric_attributes(attr: &Attributes) -> Result<crate::openapi::Attributes, Diagnostic> {
+    const NUMERIC: [&str; 5] = [
+        "minimum",
+        "maximum",
+        "exclusiveMinimum",
+        "exclusiveMaximum",
+        "multipleOf",
+    ];
+
+    for key in NUMERIC {
+        match attr.get(key) {
+            None | Some(Value::Immediate(Literal::Int(_) | Literal::Float(_))) => {}
+            Some(_) => {
+                return Err(Diagnostic::error(format!("`{key}` must be a number")));
+            }
+        }
     }
+    match attr.get("multipleOf") {
+        Some(Value::Immediate(Literal::Int(n))) if *n <= 0 => {}
+        Some(Value::Immediate(Literal::Float(n))) if *n <= 0.0 => {}
+        _ => return Ok(attributes(attr)),
+    }
+
+    Err(Diagnostic::error("`multipleOf` must be greater than 0"))
 }
 
 pub(super) fn attributes(attr: &Attributes) -> crate::openapi::Attributes {
@@ -161,10 +415,18 @@ pub(super) fn attributes(attr: &Attributes) -> crate::openapi::Attributes {
         .collect()
 }
 
-fn value_to_json(value: Value) -> serde_json::Value {
+pub(super) fn value_to_json(value: Value) -> serde_json::Value {
     match value {
         Value::Immediate(value) => literal_to_json(value),
+        // identifiers are replaced by what they name before conversion
         Value::Identifier(_) => serde_json::Value::Null,
+        Value::List(values) => serde_json::Value::from_iter(values.into_iter().map(value_to_json)),
+        Value::Object(entries) => serde_json::Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key, value_to_json(value)))
+                .collect(),
+        ),
     }
 }
 
@@ -174,14 +436,12 @@ fn literal_to_json(literal: Literal) -> serde_json::Value {
         Literal::Int(value) => serde_json::Value::from(value),
         Literal::Bool(value) => serde_json::Value::from(value),
         Literal::Float(value) => serde_json::Value::from(value),
-        Literal::List(value) => {
-            serde_json::Value::from_iter(value.into_iter().map(|v| literal_to_json(v)))
-        }
+        Literal::Null => serde_json::Value::Null,
     }
 }
 
 pub(super) trait ToReferenceOr {
     type Output: Clone + Debug + Serialize;
 
-    fn to_reference_or(&self) -> crate::openapi::ReferenceOr<Self::Output>;
+    fn to_reference_or(&self) -> Result<crate::openapi::ReferenceOr<Self::Output>, Diagnostic>;
 }
@@ -1,37 +1,62 @@
+use crate::analyzer::TAG_KEYS;
 use crate::converter::media_content;
-use crate::converter::object::{attributes, ToReferenceOr};
+use crate::converter::object::{attributes, value_to_json, ToReferenceOr};
 use crate::converter::response::parameter;
+use crate::diagnostic::Diagnostic;
 use crate::openapi::{Paths, ReferenceOr};
-use parser::{Operation, ParameterType, Parameters, Path, ResponseContent};
-use std::collections::HashMap;
+use indexmap::IndexMap;
+use parser::{
+    Attributes, Literal, Operation, ParameterType, Parameters, Path, ResponseContent, Spanned,
+    Value,
+};
 
-pub(super) fn path_values(paths: Vec<Path>) -> Paths {
-    let mut ps = HashMap::new();
+/// Attribute key of the servers of a path or an operation.
+const SERVERS_KEY: &str = "servers";
+
+pub(super) fn path_values(
+    paths: Vec<Spanned<Path>>,
+    tags: &[crate::openapi::Tag],
+) -> Result<Paths, Diagnostic> {
+    let mut ps = IndexMap::new();
 
     for p in paths {
-        ps.insert(p.name.clone(), path(p));
+        let span = p.span;
+        ps.insert(
+            p.value.name.clone(),
+            path(p.value, tags).map_err(|d| d.or_span(&span))?,
+        );
     }
 
-    Paths { content: ps }
+    Ok(Paths { content: ps })
 }
 
-fn path(path: Path) -> crate::openapi::Path {
-    crate::openapi::Path {
-        parameters: parameters(path.content.parameters),
-        get: operation(path.content.get),
-        post: operation(path.content.post),
-        put: operation(path.content.put),
-        delete: operation(path.content.delete),
-        options: operation(path.content.options),
-        head: operation(path.content.head),
-        patch: operation(path.content.patch),
-        trace: operation(path.content.trace),
-    }
+/// `servers` of the path is written on the path item, and its other attributes are defaults
+/// for its operations.
+fn path(path: Path, tags: &[crate::openapi::Tag]) -> Result<crate::openapi::Path, Diagnostic> {
+    let servers = path.attributes.get(SERVERS_KEY).cloned().map(value_to_json);
+    let defaults = &path
+        .attributes
+        .iter()
+        .filter(|(key, _)| key.as_str() != SERVERS_KEY)
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    Ok(crate::openapi::Path {
+        servers,
+        parameters: parameters(path.content.parameters)?,
+        get: operation(path.content.get, defaults, tags)?,
+        post: operation(path.content.post, defaults, tags)?,
+        put: operation(path.content.put, defaults, tags)?,
+        delete: operation(path.content.delete, defaults, tags)?,
+        options: operation(path.content.options, defaults, tags)?,
+        head: operation(path.content.head, defaults, tags)?,
+        patch: operation(path.content.patch, defaults, tags)?,
+        trace: operation(path.content.trace, defaults, tags)?,
+    })
 }
 
 fn parameters(
     parameters: Parameters,
-) -> Vec<crate::openapi::ReferenceOr<crate::openapi::Parameter>> {
+) -> Result<Vec<crate::openapi::ReferenceOr<crate::openapi::Parameter>>, Diagnostic> {
     parameters
         .parameters
         .into_iter()
@@ -39,49 +64,137 @@ fn parameters(
         .collect()
 }
 
-fn operation(operation: Option<Operation>) -> Option<crate::openapi::Operation> {
-    match operation {
-        None => None,
-        Some(op) => Some(crate::openapi::Operation {
-            operation_id: op.name,
-            tags: vec![],
-            parameters: parameters(op.parameters),
-            request_body: None,
-            responses: crate::openapi::Responses {
-                default: op.content.default.map(|r| r.to_reference_or()),
-                code: op
-                    .content
-                    .response
-                    .into_iter()
-                    .map(|(name, r)| (name, r.to_reference_or()))
-                    .collect(),
-            },
-            attributes: Default::default(),
-        }),
+/// Attributes of the path are defaults for each of its operations, overridden by the
+/// operation's doc comment and then by the operation's own attributes.
+fn operation(
+    operation: Option<Operation>,
+    defaults: &Attributes,
+    tags: &[crate::openapi::Tag],
+) -> Result<Option<crate::openapi::Operation>, Diagnostic> {
+    let op = match operation {
+        None => return Ok(None),
+        Some(op) => op,
+    };
+
+    let op_tags = match operation_tags(&op.attributes, tags)? {
+        Some(op_tags) => op_tags,
+        None => operation_tags(defaults, tags)?.unwrap_or_default(),
+    };
+
+    let mut op_attributes = attributes(&without_tags(defaults));
+    op_attributes.extend(operation_doc(&op.doc));
+    op_attributes.extend(attributes(&without_tags(&op.attributes)));
+
+    Ok(Some(crate::openapi::Operation {
+        operation_id: op.name,
+        tags: op_tags,
+        parameters: parameters(op.parameters)?,
+        request_body: op.request_body.map(|r| r.to_reference_or()).transpose()?,
+        responses: crate::openapi::Responses {
+            default: op
+                .content
+                .default
+                .map(|r| r.to_reference_or())
+                .transpose()?,
+            code: op
+                .content
+                .response
+                .into_iter()
+                .map(|(name, r)| Ok((name, r.to_reference_or()?)))
+                .collect::<Result<_, Diagnostic>>()?,
+        },
+        attributes: op_attributes,
+    }))
+}
+
+/// Tags given by `tag = X` or `tags = [X, Y]`, checked against the declared `tag` objects.
+/// `None` when neither attribute is present.
+fn operation_tags(
+    attributes: &Attributes,
+    declared: &[crate::openapi::Tag],
+) -> Result<Option<Vec<String>>, Diagnostic> {
+    let values = TAG_KEYS
+        .iter()
+        .filter_map(|key| attributes.get(*key))
+        .collect::<Vec<_>>();
+    if values.is_empty() {
+        return Ok(None);
+    }
+
+    let mut names = Vec::new();
+    for value in values {
+        tag_names(value, &mut names)?;
+    }
+
+    for name in &names {
+        if !declared.iter().any(|t| &t.name == name) {
+            return Err(Diagnostic::error(format!("tag `{name}` is not declared"))
+                .with_note(format!("declare it with `tag {name}`")));
+        }
     }
+
+    Ok(Some(names))
+}
+
+/// Names of the tags in `value`, in which the analysis has turned the tags into their names.
+fn tag_names(value: &Value, names: &mut Vec<String>) -> Result<(), Diagnostic> {
+    match value {
+        Value::Immediate(Literal::String(name)) => {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+            Ok(())
+        }
+        Value::List(values) => values.iter().try_for_each(|v| tag_names(v, names)),
+        _ => Err(Diagnostic::error(
+            "a tag must be given by its name or a list of names",
+        )),
+    }
+}
+
+fn without_tags(attributes: &Attributes) -> Attributes {
+    attributes
+        .iter()
+        .filter(|(key, _)| !TAG_KEYS.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+/// Doc comment of an operation gives its description, and the first line its summary.
+fn operation_doc(doc: &Option<String>) -> crate::openapi::Attributes {
+    let mut attributes = crate::openapi::Attributes::new();
+    if let Some(doc) = doc {
+        let summary = doc.lines().next().unwrap_or_default();
+        attributes.insert("summary".to_string(), summary.into());
+        attributes.insert("description".to_string(), doc.as_str().into());
+    }
+    attributes
 }
 
-fn response(response: &ResponseContent) -> ReferenceOr<crate::openapi::Response> {
-    ReferenceOr::Value(crate::openapi::Response {
+fn response(
+    response: &ResponseContent,
+) -> Result<ReferenceOr<crate::openapi::Response>, Diagnostic> {
+    Ok(ReferenceOr::Value(crate::openapi::Response {
         headers: response
             .headers
             .parameters
             .iter()
-            .map(|p| (p.name.clone(), parameter(p, Some(ParameterType::Header))))
-            .collect(),
-        content: media_content(&response.content),
+            .map(|p| Ok((p.name.clone(), parameter(p, Some(ParameterType::Header))?)))
+            .collect::<Result<_, Diagnostic>>()?,
+        content: media_content(&response.content)?,
         attributes: attributes(&response.attributes),
-    })
+    }))
 }
 
 impl ToReferenceOr for parser::ReferenceOr<ResponseContent> {
     type Output = crate::openapi::Response;
 
-    fn to_reference_or(&self) -> ReferenceOr<Self::Output> {
+    fn to_reference_or(&self) -> Result<ReferenceOr<Self::Output>, Diagnostic> {
         match self {
-            parser::ReferenceOr::Ref(r) => ReferenceOr::Ref {
+            parser::ReferenceOr::Ref(r) => Ok(ReferenceOr::Ref {
                 ref_path: format!("#/components/responses/{r}"),
-            },
+                description: None,
+            }),
             parser::ReferenceOr::Value(v) => response(v),
         }
     }
@@ -1,18 +1,35 @@
 use crate::converter::media_content;
-use crate::converter::object::attributes;
-use parser::{RequestBody, RequestBodyContent};
+use crate::converter::object::{attributes, ToReferenceOr};
+use crate::diagnostic::Diagnostic;
+use parser::{ReferenceOr, RequestBody, RequestBodyContent};
 
 pub(super) fn request_body(
     request_body: &RequestBody,
-) -> crate::openapi::ReferenceOr<crate::openapi::RequestBody> {
+) -> Result<crate::openapi::ReferenceOr<crate::openapi::RequestBody>, Diagnostic> {
     request_body_content(&request_body.content)
 }
 
 pub(super) fn request_body_content(
     content: &RequestBodyContent,
-) -> crate::openapi::ReferenceOr<crate::openapi::RequestBody> {
-    crate::openapi::ReferenceOr::Value(crate::openapi::RequestBody {
-        content: media_content(&content.content),
-        attributes: attributes(&content.attributes),
-    })
+) -> Result<crate::openapi::ReferenceOr<crate::openapi::RequestBody>, Diagnostic> {
+    Ok(crate::openapi::ReferenceOr::Value(
+        crate::openapi::RequestBody {
+            content: media_content(&content.content)?,
+            attributes: attributes(&content.attributes),
+        },
+    ))
+}
+
+impl ToReferenceOr for ReferenceOr<RequestBodyContent> {
+    type Output = crate::openapi::RequestBody;
+
+    fn to_reference_or(&self) -> Result<crate::openapi::ReferenceOr<Self::Output>, Diagnostic> {
+        match self {
+            ReferenceOr::Ref(r) => Ok(crate::openapi::ReferenceOr::Ref {
+                ref_path: format!("#/components/requestBodies/{r}"),
+                description: None,
+            }),
+            ReferenceOr::Value(v) => request_body_content(v),
+        }
+    }
 }
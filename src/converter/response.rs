@@ -1,36 +1,58 @@
 use crate::converter::media_content;
 use crate::converter::object::{attributes, type_with_attributes};
+use crate::diagnostic::Diagnostic;
 use crate::openapi::ParameterIn;
 use parser::{Parameter, ParameterType, Requirement, Response, ResponseContent};
-use std::collections::HashMap;
 
-pub(super) fn response(res: &Response) -> crate::openapi::Response {
+pub(super) fn response(res: &Response) -> Result<crate::openapi::Response, Diagnostic> {
     response_content(&res.content)
 }
 
-fn response_content(content: &ResponseContent) -> crate::openapi::Response {
-    crate::openapi::Response {
-        headers: HashMap::from_iter(
-            content
-                .headers
-                .parameters
-                .iter()
-                .map(|p| (p.name.clone(), parameter(p, None))),
-        ),
-        content: media_content(&content.content),
+fn response_content(content: &ResponseContent) -> Result<crate::openapi::Response, Diagnostic> {
+    Ok(crate::openapi::Response {
+        headers: content
+            .headers
+            .parameters
+            .iter()
+            .map(|p| Ok((p.name.clone(), parameter(p, None)?)))
+            .collect::<Result<_, Diagnostic>>()?,
+        content: media_content(&content.content)?,
         attributes: attributes(&content.attributes),
-    }
+    })
 }
 
 pub(super) fn parameter(
     param: &Parameter,
     pt: Option<ParameterType>,
-) -> crate::openapi::ReferenceOr<crate::openapi::Parameter> {
-    crate::openapi::ReferenceOr::Value(crate::openapi::Parameter {
-        name: param.name.clone(),
-        parameter_in: match &param.ty {
-            None => match pt {
-                None => panic!("Unknown type of parameter type. It may be generator bug"),
+) -> Result<crate::openapi::ReferenceOr<crate::openapi::Parameter>, Diagnostic> {
+    let mut param_attributes = attributes(&param.content.attributes);
+    if let Some(doc) = &param.doc {
+        param_attributes
+            .entry("description".to_string())
+            .or_insert(doc.as_str().into());
+    }
+
+    Ok(crate::openapi::ReferenceOr::Value(
+        crate::openapi::Parameter {
+            name: param.name.clone(),
+            parameter_in: match &param.ty {
+                None => match pt {
+                    None => {
+                        return Err(Diagnostic::error(format!(
+                            "cannot tell where parameter `{}` is passed",
+                            param.name
+                        ))
+                        .with_note(
+                            "declare it with `query`, `header`, `pathParameter` or `cookie`",
+                        ))
+                    }
+                    Some(p) => match p {
+                        ParameterType::Query => ParameterIn::Query,
+                        ParameterType::Header => ParameterIn::Header,
+                        ParameterType::Path => ParameterIn::Path,
+                        ParameterType::Cookie => ParameterIn::Cookie,
+                    },
+                },
                 Some(p) => match p {
                     ParameterType::Query => ParameterIn::Query,
                     ParameterType::Header => ParameterIn::Header,
@@ -38,15 +60,9 @@ pub(super) fn parameter(
                     ParameterType::Cookie => ParameterIn::Cookie,
                 },
             },
-            Some(p) => match p {
-                ParameterType::Query => ParameterIn::Query,
-                ParameterType::Header => ParameterIn::Header,
-                ParameterType::Path => ParameterIn::Path,
-                ParameterType::Cookie => ParameterIn::Cookie,
-            },
+            required: param.content.requirement == Requirement::Required,
+            schema: type_with_attributes(&param.content.content)?,
+            attributes: param_attributes,
         },
-        required: param.content.requirement == Requirement::Required,
-        schema: type_with_attributes(&param.content.content),
-        attributes: attributes(&param.content.attributes),
-    })
+    ))
 }
@@ -1,53 +1,362 @@
 use crate::converter::object::{attributes, type_with_attributes, ToReferenceOr};
-use parser::{EnumContent, ReferenceOr, Requirement, Schema, SchemaContent};
-use std::collections::HashMap;
+use crate::diagnostic::Diagnostic;
+use indexmap::IndexMap;
+use parser::{
+    Attributes, DiscriminatedUnion, EnumContent, ReferenceOr, Requirement, Schema, SchemaContent,
+    SchemaField, Type, TypeWithAttributes,
+};
+use std::collections::{HashMap, HashSet};
 
-pub(super) fn schema(schema: &Schema) -> crate::openapi::ReferenceOr<crate::openapi::Schema> {
-    schema_content(&schema.content)
+/// How a schema that `extends` another is written in the output.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum Inheritance {
+    /// `allOf` a reference to the base schema and an object with the fields the schema adds.
+    #[default]
+    AllOf,
+    /// A single object with the fields of the schema and all of its bases.
+    Flatten,
+}
+
+pub(super) fn schema(
+    schema: &Schema,
+) -> Result<crate::openapi::ReferenceOr<crate::openapi::Schema>, Diagnostic> {
+    Ok(documented(schema_content(&schema.content)?, &schema.doc))
+}
+
+/// The schema with its base replaced as `inheritance` says, after checking that the fields it
+/// redefines are compatible with those of its bases.
+pub(super) fn extends_schema(
+    schemas: &HashMap<String, Schema>,
+    schema: &Schema,
+    inheritance: Inheritance,
+) -> Result<Schema, Diagnostic> {
+    let Some(base) = &schema.base else {
+        return Ok(schema.clone());
+    };
+    let name = schema.name.clone().unwrap_or_default();
+    let mut fields = inherited_fields(schemas, base, &mut vec![name.clone()])?;
+    let SchemaContent::Definition {
+        fields: own,
+        attributes,
+    } = &schema.content
+    else {
+        return Err(not_extensible(&name));
+    };
+    override_fields(schemas, &mut fields, own, &name)?;
+
+    let content = match inheritance {
+        Inheritance::Flatten => SchemaContent::Definition {
+            fields: fields.into_iter().map(|(f, _)| f.clone()).collect(),
+            attributes: attributes.clone(),
+        },
+        Inheritance::AllOf => {
+            let mut all_of = vec![TypeWithAttributes {
+                target_type: Type::Schema(ReferenceOr::Ref(base.clone())),
+                attributes: IndexMap::new(),
+            }];
+            if !own.is_empty() {
+                all_of.push(TypeWithAttributes {
+                    target_type: Type::Schema(ReferenceOr::Value(SchemaContent::Definition {
+                        fields: own.clone(),
+                        attributes: IndexMap::new(),
+                    })),
+                    attributes: IndexMap::new(),
+                });
+            }
+            SchemaContent::Typedef(Box::new(TypeWithAttributes {
+                target_type: Type::AllOf(all_of),
+                attributes: attributes.clone(),
+            }))
+        }
+    };
+
+    Ok(Schema {
+        base: None,
+        content,
+        ..schema.clone()
+    })
+}
+
+/// Fields of the schema `name` and its bases, with the name of the schema declaring each.
+fn inherited_fields<'a>(
+    schemas: &'a HashMap<String, Schema>,
+    name: &'a str,
+    visiting: &mut Vec<String>,
+) -> Result<Vec<(&'a SchemaField, &'a str)>, Diagnostic> {
+    if visiting.iter().any(|v| v == name) {
+        visiting.push(name.to_string());
+        return Err(
+            Diagnostic::error(format!("schema `{}` extends itself", visiting[0]))
+                .with_note(format!("extends chain: {}", visiting.join(" -> "))),
+        );
+    }
+    visiting.push(name.to_string());
+
+    let schema = schemas
+        .get(name)
+        .ok_or_else(|| Diagnostic::error(format!("base schema `{name}` is not defined")))?;
+    let SchemaContent::Definition { fields, .. } = &schema.content else {
+        return Err(not_extensible(name));
+    };
+    let mut inherited = match &schema.base {
+        None => vec![],
+        Some(base) => inherited_fields(schemas, base, visiting)?,
+    };
+    override_fields(schemas, &mut inherited, fields, name)?;
+
+    Ok(inherited)
+}
+
+fn not_extensible(name: &str) -> Diagnostic {
+    Diagnostic::error(format!("schema `{name}` cannot be extended"))
+        .with_note("only schemas defined with `{ ... }` have fields to extend")
+}
+
+/// Adds the fields declared by `schema` to the inherited ones, replacing those it redefines.
+fn override_fields<'a>(
+    schemas: &HashMap<String, Schema>,
+    inherited: &mut Vec<(&'a SchemaField, &'a str)>,
+    fields: &'a [SchemaField],
+    schema: &'a str,
+) -> Result<(), Diagnostic> {
+    for f in fields {
+        let Some(entry) = inherited.iter_mut().find(|(g, _)| g.name == f.name) else {
+            inherited.push((f, schema));
+            continue;
+        };
+
+        let (base_field, base) = *entry;
+        if base_field.requirement == Requirement::Required && f.requirement == Requirement::Optional
+        {
+            return Err(Diagnostic::error(format!(
+                "field `{}` of schema `{schema}` is optional, but required by `{base}`",
+                f.name
+            )));
+        }
+        let (base_type, own_type) = (
+            &base_field.target_type.target_type,
+            &f.target_type.target_type,
+        );
+        if !compatible(schemas, base_type, own_type) {
+            return Err(Diagnostic::error(format!(
+                "field `{}` of schema `{schema}` redefines the one of `{base}` with an incompatible type",
+                f.name
+            ))
+            .with_note(format!(
+                "`{base}` declares it as {}, and `{schema}` as {}",
+                type_name(schemas, base_type),
+                type_name(schemas, own_type),
+            )));
+        }
+        *entry = (f, schema);
+    }
+
+    Ok(())
+}
+
+/// Whether values of `own` are also values of `base`, as far as can be told from their types.
+fn compatible(schemas: &HashMap<String, Schema>, base: &Type, own: &Type) -> bool {
+    match (base, own) {
+        (Type::List { item_type: base }, Type::List { item_type: own }) => {
+            compatible(schemas, &base.target_type, &own.target_type)
+        }
+        (Type::Schema(ReferenceOr::Ref(base)), Type::Schema(ReferenceOr::Ref(own)))
+            if has_fields(schemas, base) && has_fields(schemas, own) =>
+        {
+            extends(schemas, own, base, &mut HashSet::new())
+        }
+        _ => match (
+            json_type(schemas, base, &mut HashSet::new()),
+            json_type(schemas, own, &mut HashSet::new()),
+        ) {
+            (Some(base), Some(own)) => base == own || (base == "number" && own == "integer"),
+            // compositions may hold values of any type
+            _ => true,
+        },
+    }
+}
+
+fn has_fields(schemas: &HashMap<String, Schema>, name: &str) -> bool {
+    matches!(
+        schemas.get(name).map(|s| &s.content),
+        Some(SchemaContent::Definition { .. })
+    )
+}
+
+/// Whether the schema `name` is `base` or extends it.
+fn extends<'a>(
+    schemas: &'a HashMap<String, Schema>,
+    name: &'a str,
+    base: &str,
+    visited: &mut HashSet<&'a str>,
+) -> bool {
+    if name == base {
+        return true;
+    }
+    match schemas.get(name).and_then(|s| s.base.as_deref()) {
+        Some(next) if visited.insert(next) => extends(schemas, next, base, visited),
+        _ => false,
+    }
+}
+
+/// The `type` of the values of `ty`, or `None` for compositions.
+fn json_type<'a>(
+    schemas: &'a HashMap<String, Schema>,
+    ty: &'a Type,
+    visited: &mut HashSet<&'a str>,
+) -> Option<&'static str> {
+    let content = match ty {
+        Type::Integer { .. } => return Some("integer"),
+        Type::Number { .. } => return Some("number"),
+        Type::String { .. } | Type::Enum(_) => return Some("string"),
+        Type::Bool => return Some("boolean"),
+        Type::List { .. } => return Some("array"),
+        Type::Object => return Some("object"),
+        Type::Schema(ReferenceOr::Ref(name)) if visited.insert(name) => &schemas.get(name)?.content,
+        Type::Schema(ReferenceOr::Value(content)) => content,
+        _ => return None,
+    };
+    match content {
+        SchemaContent::Definition { .. } => Some("object"),
+        SchemaContent::Typedef(twa) => json_type(schemas, &twa.target_type, visited),
+    }
+}
+
+fn type_name(schemas: &HashMap<String, Schema>, ty: &Type) -> String {
+    match ty {
+        Type::Schema(ReferenceOr::Ref(name)) => format!("`{name}`"),
+        _ => match json_type(schemas, ty, &mut HashSet::new()) {
+            Some(t) => format!("`{t}`"),
+            None => "a composition".to_string(),
+        },
+    }
+}
+
+/// Uses the doc comment as description unless one is given by attributes.
+pub(super) fn documented(
+    schema: crate::openapi::ReferenceOr<crate::openapi::Schema>,
+    doc: &Option<String>,
+) -> crate::openapi::ReferenceOr<crate::openapi::Schema> {
+    let doc = match doc {
+        None => return schema,
+        Some(doc) => doc.clone(),
+    };
+
+    match schema {
+        crate::openapi::ReferenceOr::Ref {
+            ref_path,
+            description,
+        } => crate::openapi::ReferenceOr::Ref {
+            ref_path,
+            description: description.or(Some(doc)),
+        },
+        crate::openapi::ReferenceOr::Value(mut schema) => {
+            schema
+                .attributes_mut()
+                .entry("description".to_string())
+                .or_insert(doc.into());
+            crate::openapi::ReferenceOr::Value(schema)
+        }
+    }
 }
 
 pub(super) fn schema_content(
     content: &SchemaContent,
-) -> crate::openapi::ReferenceOr<crate::openapi::Schema> {
+) -> Result<crate::openapi::ReferenceOr<crate::openapi::Schema>, Diagnostic> {
     match &content {
-        SchemaContent::Typedef(ty) => type_with_attributes(&ty),
+        SchemaContent::Typedef(ty) => type_with_attributes(ty),
         SchemaContent::Definition {
             fields,
             attributes: attr,
-        } => crate::openapi::ReferenceOr::Value(crate::openapi::Schema::Object {
-            required: fields
-                .iter()
-                .filter(|f| f.requirement == Requirement::Required)
-                .map(|f| f.name.clone())
-                .collect(),
-            properties: HashMap::from_iter(
-                fields
+        } => Ok(crate::openapi::ReferenceOr::Value(
+            crate::openapi::Schema::Object {
+                required: fields
                     .iter()
-                    .map(|f| (f.name.clone(), type_with_attributes(&f.target_type))),
-            ),
-            attributes: attributes(attr),
-        }),
+                    .filter(|f| f.requirement == Requirement::Required)
+                    .map(|f| f.name.clone())
+                    .collect(),
+                properties: fields
+                    .iter()
+                    .map(|f| {
+                        let ty = type_with_attributes(&f.target_type)?;
+                        Ok((f.name.clone(), documented(ty, &f.doc)))
+                    })
+                    .collect::<Result<_, Diagnostic>>()?,
+                attributes: attributes(attr),
+            },
+        )),
     }
 }
 
 pub(super) fn enum_content(
     content: &EnumContent,
 ) -> crate::openapi::ReferenceOr<crate::openapi::Schema> {
+    let mut attributes = attributes(&content.attributes);
+    if content.selection.iter().any(|m| m.doc.is_some()) {
+        attributes
+            .entry("x-enum-descriptions".to_string())
+            .or_insert_with(|| {
+                content
+                    .selection
+                    .iter()
+                    .map(|m| m.doc.clone().unwrap_or_default())
+                    .collect()
+            });
+    }
+
     crate::openapi::ReferenceOr::Value(crate::openapi::Schema::String {
         format: None,
-        selection: Some(content.selection.clone()),
-        attributes: attributes(&content.attributes),
+        selection: Some(content.selection.iter().map(|m| m.name.clone()).collect()),
+        attributes,
+    })
+}
+
+/// `oneOf` the variants of the union, with a `discriminator` mapping each value of the property
+/// to its variant.
+pub(super) fn discriminated(
+    union: &DiscriminatedUnion,
+    attr: &Attributes,
+) -> crate::openapi::ReferenceOr<crate::openapi::Schema> {
+    let mut variants = Vec::<&str>::new();
+    for (_, schema) in &union.mapping {
+        if !variants.contains(&schema.as_str()) {
+            variants.push(schema);
+        }
+    }
+
+    crate::openapi::ReferenceOr::Value(crate::openapi::Schema::OneOf {
+        one_of: variants
+            .into_iter()
+            .map(|schema| crate::openapi::ReferenceOr::Ref {
+                ref_path: ref_path(schema),
+                description: None,
+            })
+            .collect(),
+        discriminator: Some(crate::openapi::Discriminator {
+            property_name: union.property.clone(),
+            mapping: union
+                .mapping
+                .iter()
+                .map(|(value, schema)| (value.clone(), ref_path(schema)))
+                .collect(),
+        }),
+        attributes: attributes(attr),
     })
 }
 
+fn ref_path(schema: &str) -> String {
+    format!("#/components/schemas/{schema}")
+}
+
 impl ToReferenceOr for ReferenceOr<SchemaContent> {
     type Output = crate::openapi::Schema;
 
-    fn to_reference_or(&self) -> crate::openapi::ReferenceOr<Self::Output> {
+    fn to_reference_or(&self) -> Result<crate::openapi::ReferenceOr<Self::Output>, Diagnostic> {
         match self {
-            ReferenceOr::Ref(r) => crate::openapi::ReferenceOr::Ref {
-                ref_path: format!("#/components/schemas/{r}"),
-            },
+            ReferenceOr::Ref(r) => Ok(crate::openapi::ReferenceOr::Ref {
+                ref_path: ref_path(r),
+                description: None,
+            }),
             ReferenceOr::Value(v) => schema_content(v),
         }
     }
@@ -0,0 +1,111 @@
+use parser::{ParseError, Span};
+use std::collections::HashMap;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Error or warning reported to the user, optionally pointing into a source file.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    // boxed to keep `Result<_, Diagnostic>` small
+    pub span: Option<Box<Span>>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            message: message.into(),
+            span: None,
+            notes: vec![],
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Diagnostic::error(message)
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    pub fn with_span(mut self, span: &Span) -> Self {
+        self.span = Some(Box::new(span.clone()));
+        self
+    }
+
+    /// Attaches `span` unless a more precise location is already known.
+    pub fn or_span(mut self, span: &Span) -> Self {
+        if self.span.is_none() {
+            self.span = Some(Box::new(span.clone()));
+        }
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Renders the diagnostic like rustc does, quoting the offending source line
+    /// when its content is found in `sources` (keyed by file path).
+    pub fn render(&self, sources: &HashMap<String, String>) -> String {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let mut out = format!("{severity}: {}\n", self.message);
+
+        let gutter = match &self.span {
+            None => 0,
+            Some(span) => span.start.line.to_string().len(),
+        };
+        let pad = " ".repeat(gutter);
+
+        if let Some(span) = &self.span {
+            out += &format!("{pad}--> {span}\n");
+
+            let line = sources
+                .get(&span.file)
+                .and_then(|s| s.split('\n').nth(span.start.line - 1))
+                .map(|l| l.trim_end_matches('\r'));
+            if let Some(line) = line {
+                let width = if span.end.line == span.start.line {
+                    (span.end.column - span.start.column).max(1)
+                } else {
+                    (line.chars().count() + 1).saturating_sub(span.start.column)
+                };
+                let indent = line
+                    .chars()
+                    .take(span.start.column - 1)
+                    .map(|c| if c == '\t' { '\t' } else { ' ' })
+                    .collect::<String>();
+
+                out += &format!("{pad} |\n");
+                out += &format!("{:>gutter$} | {line}\n", span.start.line);
+                out += &format!("{pad} | {indent}{}\n", "^".repeat(width.max(1)));
+            }
+        }
+
+        for note in &self.notes {
+            out += &format!("{pad} = note: {note}\n");
+        }
+
+        out
+    }
+}
+
+impl From<ParseError> for Diagnostic {
+    fn from(value: ParseError) -> Self {
+        Diagnostic::error(value.to_string()).with_span(&value.span)
+    }
+}
@@ -0,0 +1,384 @@
+use crate::analyzer::analyze;
+use crate::converter::{generate, GenericNaming, Inheritance};
+use crate::diagnostic::Diagnostic;
+use crate::import_graph::ImportGraph;
+use crate::linker::{link, SourceFile};
+use crate::openapi::OpenApi;
+use crate::source::{DiskProvider, SourceProvider};
+use log::debug;
+use parser::{parse, SourceFileContent, Span};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::{env, iter};
+
+/// Environment variable holding directories searched for imported files, separated like
+/// `PATH`. See [`Generator::include_dirs_from_env`].
+pub const SEARCH_PATH_VAR: &str = "OPENAPI_GEN_PATH";
+
+/// Builds an OpenAPI document from source files.
+///
+/// The input files are read with their imports as if they were imported by one file, in the
+/// order they are added.
+pub struct Generator {
+    inputs: Vec<Input>,
+    include: Vec<PathBuf>,
+    config: Option<String>,
+    sort: bool,
+    inheritance: Inheritance,
+    generic_naming: GenericNaming,
+    provider: Box<dyn SourceProvider>,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator {
+            inputs: vec![],
+            include: vec![],
+            config: None,
+            sort: false,
+            inheritance: Inheritance::default(),
+            generic_naming: GenericNaming::default(),
+            provider: Box::new(DiskProvider),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Input {
+    File(PathBuf),
+    Source { name: String, content: String },
+}
+
+/// Value produced by a [`Generator`], with the warnings reported on the way.
+#[derive(Debug)]
+pub struct Output<T> {
+    pub value: T,
+    pub warnings: Vec<Diagnostic>,
+    /// Contents of the source files keyed by path, to render the warnings with.
+    pub sources: HashMap<String, String>,
+}
+
+impl<T> Output<T> {
+    pub fn render_warnings(&self) -> String {
+        self.warnings
+            .iter()
+            .map(|d| d.render(&self.sources))
+            .collect()
+    }
+}
+
+/// Failure of a [`Generator`]: the errors found, and the warnings reported before them.
+#[derive(Debug)]
+pub struct Error {
+    pub diagnostics: Vec<Diagnostic>,
+    /// Contents of the source files keyed by path, to render the diagnostics with.
+    pub sources: HashMap<String, String>,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for d in &self.diagnostics {
+            write!(f, "{}", d.render(&self.sources))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Diagnostic> for Error {
+    fn from(value: Diagnostic) -> Self {
+        Error {
+            diagnostics: vec![value],
+            sources: HashMap::new(),
+        }
+    }
+}
+
+impl Generator {
+    pub fn new() -> Self {
+        Generator::default()
+    }
+
+    /// Adds a source file to read from the [`SourceProvider`].
+    pub fn source_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.inputs.push(Input::File(path.into()));
+        self
+    }
+
+    /// Adds a source file held in memory. `name` is used in diagnostics, and files it imports
+    /// are searched relative to it with the [`SourceProvider`].
+    pub fn source_str(mut self, name: impl Into<String>, content: impl Into<String>) -> Self {
+        self.inputs.push(Input::Source {
+            name: name.into(),
+            content: content.into(),
+        });
+        self
+    }
+
+    /// Adds a directory searched for imported files, after the directory of the importing
+    /// file and the directories added before.
+    pub fn include_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.include.push(dir.into());
+        self
+    }
+
+    /// Adds the directories listed in the [`SEARCH_PATH_VAR`] environment variable.
+    pub fn include_dirs_from_env(mut self) -> Self {
+        if let Some(paths) = env::var_os(SEARCH_PATH_VAR) {
+            self.include
+                .extend(env::split_paths(&paths).filter(|p| !p.as_os_str().is_empty()));
+        }
+        self
+    }
+
+    /// Reads source files through `provider` instead of from the file system.
+    pub fn provider(mut self, provider: impl SourceProvider + 'static) -> Self {
+        self.provider = Box::new(provider);
+        self
+    }
+
+    /// Selects the info config to use instead of the `default info`.
+    pub fn config(mut self, name: Option<String>) -> Self {
+        self.config = name;
+        self
+    }
+
+    /// Sorts the document by name instead of keeping the order of declaration. See
+    /// [`OpenApi::sort`].
+    pub fn sort(mut self, sort: bool) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Selects how schemas that `extends` another are written, as `allOf` by default.
+    pub fn inheritance(mut self, inheritance: Inheritance) -> Self {
+        self.inheritance = inheritance;
+        self
+    }
+
+    /// Selects how the instances of generic schemas are named, as `PageUser` by default.
+    pub fn generic_naming(mut self, naming: GenericNaming) -> Self {
+        self.generic_naming = naming;
+        self
+    }
+
+    /// Reads, checks and converts the source files.
+    pub fn generate(&self) -> Result<Output<OpenApi>, Error> {
+        let mut sources = HashMap::new();
+        let mut diagnostics = vec![];
+        let generated = self.generate_impl(&mut sources, &mut diagnostics);
+        finish(generated, sources, diagnostics)
+    }
+
+    fn generate_impl(
+        &self,
+        sources: &mut HashMap<String, String>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Result<OpenApi, Diagnostic> {
+        let files = self.load(sources)?;
+        let root_dir = root_dir(&files);
+
+        diagnostics.extend(ImportGraph::new(&files, &root_dir).cycles());
+        let (mut objects, link_diagnostics) = link(files, &root_dir);
+        diagnostics.extend(link_diagnostics);
+        diagnostics.extend(analyze(&mut objects));
+
+        let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+        if errors > 0 {
+            return Err(Diagnostic::error(format!(
+                "aborting due to {errors} previous error{}",
+                if errors == 1 { "" } else { "s" }
+            )));
+        }
+
+        let mut openapi = generate(
+            objects,
+            self.config.clone(),
+            self.inheritance,
+            self.generic_naming,
+        )?;
+        if self.sort {
+            openapi.sort();
+        }
+        Ok(openapi)
+    }
+
+    /// Reads the source files and returns the graph of their imports.
+    pub fn import_graph(&self) -> Result<Output<ImportGraph>, Error> {
+        let mut sources = HashMap::new();
+        let mut diagnostics = vec![];
+        let graph = self.load(&mut sources).map(|files| {
+            let graph = ImportGraph::new(&files, &root_dir(&files));
+            diagnostics.extend(graph.cycles());
+            graph
+        });
+        finish(graph, sources, diagnostics)
+    }
+
+    fn load(&self, sources: &mut HashMap<String, String>) -> Result<Vec<SourceFile>, Diagnostic> {
+        let mut files = Vec::<SourceFile>::new();
+        let mut loaded_files = HashMap::<String, usize>::new();
+        let mut imported_but_unread = self
+            .inputs
+            .iter()
+            .rev()
+            .map(|input| (input.clone(), None))
+            .collect::<Vec<_>>();
+
+        while let Some((input, imported_at)) = imported_but_unread.pop() {
+            let key = match &input {
+                Input::File(path) => self
+                    .provider
+                    .canonicalize(path)
+                    .unwrap_or_else(|_| path.clone())
+                    .to_string_lossy()
+                    .to_string(),
+                Input::Source { name, .. } => name.clone(),
+            };
+            if let Some(&loaded) = loaded_files.get(&key) {
+                debug!("skip imported file: {key} (already loaded)");
+                if imported_at.is_none() {
+                    files[loaded].input = true;
+                }
+                continue;
+            }
+
+            let (file, file_content) = match input {
+                Input::File(path) => {
+                    load_and_parse(&path, &*self.provider, imported_at.as_ref(), sources)?
+                }
+                Input::Source { name, content } => {
+                    let parsed = parse(&name, &content);
+                    sources.insert(name.clone(), content);
+                    (name, parsed?)
+                }
+            };
+            let parent = Path::new(file.as_str()).parent().unwrap_or(Path::new(""));
+
+            let mut imports = Vec::new();
+            for i in file_content.imports {
+                let imported = resolve_import(
+                    &i.value.file,
+                    parent,
+                    &self.include,
+                    &*self.provider,
+                    &i.span,
+                )?;
+                imports.push((i, imported));
+            }
+            // reversed so that imported files are read, and their objects ordered, as written
+            for (i, imported) in imports.iter().rev() {
+                imported_but_unread
+                    .push((Input::File(PathBuf::from(imported)), Some(i.span.clone())));
+            }
+
+            loaded_files.insert(key, files.len());
+            files.push(SourceFile {
+                path: file,
+                input: imported_at.is_none(),
+                imports,
+                objects: file_content.objects,
+            });
+        }
+
+        Ok(files)
+    }
+}
+
+fn finish<T>(
+    result: Result<T, Diagnostic>,
+    sources: HashMap<String, String>,
+    mut diagnostics: Vec<Diagnostic>,
+) -> Result<Output<T>, Error> {
+    match result {
+        Ok(value) => Ok(Output {
+            value,
+            warnings: diagnostics,
+            sources,
+        }),
+        Err(d) => {
+            diagnostics.push(d);
+            Err(Error {
+                diagnostics,
+                sources,
+            })
+        }
+    }
+}
+
+/// Directory of the first input file, which the names of the other files are relative to.
+fn root_dir(files: &[SourceFile]) -> PathBuf {
+    files
+        .first()
+        .and_then(|f| Path::new(&f.path).parent())
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+/// Finds the file imported as `file` from a file in `dir`, trying `dir` first and then the
+/// search path in order.
+fn resolve_import(
+    file: &str,
+    dir: &Path,
+    search_path: &[PathBuf],
+    provider: &dyn SourceProvider,
+    imported_at: &Span,
+) -> Result<String, Diagnostic> {
+    let candidates = iter::once(dir)
+        .chain(search_path.iter().map(PathBuf::as_path))
+        .map(|d| d.join(file))
+        .collect::<Vec<_>>();
+
+    match candidates
+        .iter()
+        .find_map(|c| provider.canonicalize(c).ok())
+    {
+        Some(found) => Ok(found.to_string_lossy().to_string()),
+        None => {
+            let mut d = Diagnostic::error(format!("cannot find imported file `{file}`"))
+                .with_span(imported_at);
+            for c in &candidates {
+                d = d.with_note(format!("tried `{}`", c.display()));
+            }
+            if search_path.is_empty() {
+                d = d.with_note(format!(
+                    "add directories to search with `--include` or `{SEARCH_PATH_VAR}`"
+                ));
+            }
+            Err(d)
+        }
+    }
+}
+
+fn load_and_parse(
+    path: &Path,
+    provider: &dyn SourceProvider,
+    imported_at: Option<&Span>,
+    sources: &mut HashMap<String, String>,
+) -> Result<(String, SourceFileContent), Diagnostic> {
+    let read_error = |e: std::io::Error| {
+        let d = Diagnostic::error(format!("cannot read `{}`: {e}", path.display()));
+        match imported_at {
+            None => d,
+            Some(span) => d.with_span(span),
+        }
+    };
+
+    let path = provider.canonicalize(path).map_err(read_error)?;
+    let content = provider.read(&path).map_err(read_error)?;
+    let file = path.to_string_lossy().to_string();
+    debug!("load imported file: {file} ({} bytes)", content.len());
+
+    let parsed = parse(file.as_str(), content.as_str()).map_err(|e| {
+        let d = Diagnostic::from(e);
+        match imported_at {
+            None => d,
+            Some(span) => d.with_note(format!("imported from {span}")),
+        }
+    });
+    sources.insert(file.clone(), content);
+
+    Ok((file, parsed?))
+}
@@ -0,0 +1,173 @@
+use crate::diagnostic::Diagnostic;
+use crate::linker::SourceFile;
+use parser::{ImportItems, Span};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Files loaded for the input files and the imports between them.
+pub struct ImportGraph {
+    /// Paths relative to the directory of the first input file, or the whole paths of the
+    /// files outside of it, in load order.
+    files: Vec<String>,
+    inputs: Vec<usize>,
+    /// Imports of each file, in the order they are written.
+    edges: Vec<Vec<Edge>>,
+}
+
+struct Edge {
+    to: usize,
+    items: ImportItems,
+    span: Span,
+}
+
+impl ImportGraph {
+    /// Builds the graph of `files`, in load order.
+    pub(crate) fn new(files: &[SourceFile], root_dir: &Path) -> Self {
+        let index = files
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (f.path.as_str(), i))
+            .collect::<HashMap<_, _>>();
+
+        ImportGraph {
+            files: files
+                .iter()
+                .map(|f| relative_path(Path::new(&f.path), root_dir))
+                .collect(),
+            inputs: (0..files.len()).filter(|i| files[*i].input).collect(),
+            edges: files
+                .iter()
+                .map(|f| {
+                    f.imports
+                        .iter()
+                        .map(|(import, path)| Edge {
+                            to: index[path.as_str()],
+                            items: import.value.items.clone(),
+                            span: import.span.clone(),
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Warns about every import that leads back to a file importing it.
+    pub(crate) fn cycles(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        let mut done = vec![false; self.files.len()];
+        let mut stack = vec![];
+        for input in &self.inputs {
+            if !done[*input] {
+                self.find_cycles(*input, &mut stack, &mut done, &mut diagnostics);
+            }
+        }
+        diagnostics
+    }
+
+    fn find_cycles(
+        &self,
+        file: usize,
+        stack: &mut Vec<usize>,
+        done: &mut [bool],
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        stack.push(file);
+        for edge in &self.edges[file] {
+            if let Some(start) = stack.iter().position(|f| *f == edge.to) {
+                let cycle = stack[start..]
+                    .iter()
+                    .chain([&edge.to])
+                    .map(|f| format!("`{}`", self.files[*f]))
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                diagnostics.push(
+                    Diagnostic::warning(format!("import cycle: {cycle}"))
+                        .with_span(&edge.span)
+                        .with_note("each file is loaded once, so the cycle is ignored"),
+                );
+            } else if !done[edge.to] {
+                self.find_cycles(edge.to, stack, done, diagnostics);
+            }
+        }
+        stack.pop();
+        done[file] = true;
+    }
+
+    /// Renders the graph as a tree from each input file, like `cargo tree` does. Files
+    /// already shown are marked with `(*)` instead of repeating their imports.
+    pub fn to_tree(&self) -> String {
+        let mut out = String::new();
+        let mut shown = vec![false; self.files.len()];
+        for input in &self.inputs {
+            let repeated = if shown[*input] { " (*)" } else { "" };
+            let _ = writeln!(out, "{}{repeated}", self.files[*input]);
+            if !shown[*input] {
+                shown[*input] = true;
+                self.write_tree(*input, "", &mut shown, &mut out);
+            }
+        }
+        out
+    }
+
+    fn write_tree(&self, file: usize, prefix: &str, shown: &mut [bool], out: &mut String) {
+        let edges = &self.edges[file];
+        for (i, edge) in edges.iter().enumerate() {
+            let last = i + 1 == edges.len();
+            let repeated = if shown[edge.to] { " (*)" } else { "" };
+            let _ = writeln!(
+                out,
+                "{prefix}{}{}{}{repeated}",
+                if last { "└── " } else { "├── " },
+                self.files[edge.to],
+                items_label(&edge.items)
+                    .map(|l| format!(" ({l})"))
+                    .unwrap_or_default(),
+            );
+            if !shown[edge.to] {
+                shown[edge.to] = true;
+                let prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+                self.write_tree(edge.to, &prefix, shown, out);
+            }
+        }
+    }
+
+    /// Renders the graph in the Graphviz DOT language, with the files as nodes labelled by
+    /// their paths.
+    pub fn to_dot(&self) -> String {
+        let mut out = "digraph imports {\n".to_string();
+        for (i, file) in self.files.iter().enumerate() {
+            let _ = writeln!(out, "    file{i} [label={}];", dot_string(file));
+        }
+        for (from, edges) in self.edges.iter().enumerate() {
+            for edge in edges {
+                let label = items_label(&edge.items)
+                    .map(|l| format!(" [label={}]", dot_string(&l)))
+                    .unwrap_or_default();
+                let _ = writeln!(out, "    file{from} -> file{}{label};", edge.to);
+            }
+        }
+        out += "}\n";
+        out
+    }
+}
+
+/// Path of `path` relative to `root_dir`, or the whole path when it is outside `root_dir`.
+pub(crate) fn relative_path(path: &Path, root_dir: &Path) -> String {
+    path.strip_prefix(root_dir)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+fn items_label(items: &ImportItems) -> Option<String> {
+    match items {
+        ImportItems::All => None,
+        ImportItems::Namespace(ns) => Some(format!("as {ns}")),
+        ImportItems::Selected(names) => Some(format!("{{ {} }}", names.join(", "))),
+    }
+}
+
+fn dot_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
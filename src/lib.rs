@@ -0,0 +1,21 @@
+//! Generates OpenAPI documents from `.oai` source files.
+//!
+//! [`Generator`] loads the source files and their imports, checks them and converts them into
+//! an [`openapi::OpenApi`] document, which can be written as YAML or JSON with
+//! [`openapi::OpenApi::write`].
+
+mod analyzer;
+mod converter;
+mod diagnostic;
+mod generator;
+mod import_graph;
+mod linker;
+pub mod openapi;
+mod source;
+
+pub use converter::{GenericNaming, Inheritance};
+pub use diagnostic::{Diagnostic, Severity};
+pub use generator::{Error, Generator, Output, SEARCH_PATH_VAR};
+pub use import_graph::ImportGraph;
+pub use openapi::Format;
+pub use source::{DiskProvider, MemoryProvider, SourceProvider};
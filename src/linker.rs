@@ -0,0 +1,298 @@
+use crate::analyzer::{defined_name, SECURITY_KEY};
+use crate::diagnostic::Diagnostic;
+use crate::import_graph::relative_path;
+use parser::visit::{ReferenceKind, VisitMut};
+use parser::{Attributes, Import, ImportItems, Object, Spanned, Value};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A parsed source file, with each import paired with the path of the file it loads.
+pub(crate) struct SourceFile {
+    pub path: String,
+    /// Whether the file was given as input rather than only imported.
+    pub input: bool,
+    pub imports: Vec<(Spanned<Import>, String)>,
+    pub objects: Vec<Spanned<Object>>,
+}
+
+/// Links the objects of the loaded files into one namespace keyed by component names.
+///
+/// Definitions of the input files and of files imported with a plain `import "file"` are
+/// visible from every file. Definitions of files imported only `as` a namespace or
+/// selectively are visible only where they are imported, and keep their name as component
+/// key unless another file defines the same name, in which case the key is qualified by the
+/// path of the file relative to `root_dir`, as in `common.errors.NotFound`, or for files
+/// outside `root_dir` by the namespace they are imported as.
+pub(crate) fn link(
+    files: Vec<SourceFile>,
+    root_dir: &Path,
+) -> (Vec<Spanned<Object>>, Vec<Diagnostic>) {
+    let index = files
+        .iter()
+        .enumerate()
+        .map(|(i, f)| (f.path.clone(), i))
+        .collect::<HashMap<_, _>>();
+
+    let mut global = files.iter().map(|f| f.input).collect::<Vec<_>>();
+    for f in &files {
+        for (import, path) in &f.imports {
+            if matches!(import.value.items, ImportItems::All) {
+                global[index[path]] = true;
+            }
+        }
+    }
+
+    let mut aliases = HashMap::<usize, &str>::new();
+    for f in &files {
+        for (import, path) in &f.imports {
+            if let ImportItems::Namespace(ns) = &import.value.items {
+                aliases.entry(index[path]).or_insert(ns);
+            }
+        }
+    }
+
+    let modules = files
+        .iter()
+        .enumerate()
+        .map(|(i, f)| Module::new(f, root_dir, aliases.get(&i).copied()))
+        .collect::<Vec<_>>();
+
+    let mut defining_files = HashMap::<&str, usize>::new();
+    for m in &modules {
+        for name in &m.names {
+            *defining_files.entry(name).or_default() += 1;
+        }
+    }
+    let qualified = |file: usize, name: &str| !global[file] && defining_files[name] > 1;
+
+    let mut diagnostics = vec![];
+
+    // qualifiers of files outside `root_dir` are not paths, so they may still collide
+    let mut collided = Vec::new();
+    let mut qualified_keys = HashMap::<String, usize>::new();
+    for (i, m) in modules.iter().enumerate() {
+        for name in m.names.iter().filter(|n| qualified(i, n)) {
+            let key = format!("{}.{name}", m.qualifier);
+            match qualified_keys.get(&key) {
+                Some(&first) => {
+                    let mut d = Diagnostic::error(format!(
+                        "`{name}` of `{}` and `{}` would both be named `{key}`",
+                        files[first].path, files[i].path
+                    ))
+                    .with_note("import one of the files `as` another namespace to rename it");
+                    if let Some(o) = files[i]
+                        .objects
+                        .iter()
+                        .find(|o| defined_name(&o.value).is_some_and(|(n, _)| n == name))
+                    {
+                        d = d.with_span(&o.span);
+                    }
+                    diagnostics.push(d);
+                    collided.push((i, name.as_str()));
+                }
+                None => {
+                    qualified_keys.insert(key, i);
+                }
+            }
+        }
+    }
+
+    let key = |file: usize, name: &str| {
+        if !qualified(file, name) {
+            name.to_string()
+        } else if collided.contains(&(file, name)) {
+            // kept apart so that the collision is not reported again as a duplicate definition
+            format!("{}.{name}#{file}", modules[file].qualifier)
+        } else {
+            format!("{}.{name}", modules[file].qualifier)
+        }
+    };
+
+    let mut global_scope = HashMap::new();
+    let mut private = HashMap::new();
+    for (i, m) in modules.iter().enumerate() {
+        for name in &m.names {
+            if global[i] {
+                global_scope
+                    .entry(name.clone())
+                    .or_insert_with(|| key(i, name));
+            } else {
+                private.entry(name.clone()).or_insert(i);
+            }
+        }
+    }
+
+    let mut objects = vec![];
+
+    for (i, f) in files.into_iter().enumerate() {
+        let mut scope = Scope {
+            own: modules[i]
+                .names
+                .iter()
+                .map(|n| (n.clone(), key(i, n)))
+                .collect(),
+            ..Scope::default()
+        };
+
+        for (import, path) in &f.imports {
+            let target = index[path];
+            match &import.value.items {
+                ImportItems::All => {}
+                ImportItems::Namespace(ns) => {
+                    let names = modules[target].names.iter();
+                    scope.namespaces.insert(
+                        ns.clone(),
+                        names.map(|n| (n.clone(), key(target, n))).collect(),
+                    );
+                }
+                ImportItems::Selected(names) => {
+                    for name in names {
+                        if modules[target].names.contains(name) {
+                            scope.selected.insert(name.clone(), key(target, name));
+                        } else {
+                            diagnostics.push(
+                                Diagnostic::error(format!(
+                                    "`{name}` is not defined in `{}`",
+                                    import.value.file
+                                ))
+                                .with_span(&import.span),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        for mut o in f.objects {
+            if let Some(name) = defined_name_mut(&mut o.value) {
+                *name = key(i, name);
+            }
+            References(|r: &mut String| {
+                if let Some(k) = scope.get(r).or_else(|| global_scope.get(r.as_str())) {
+                    *r = k.clone();
+                } else if let Some(&defined_in) = private.get(r.as_str()) {
+                    let file = &modules[defined_in].file;
+                    diagnostics.push(
+                        Diagnostic::error(format!(
+                            "`{r}` is defined in `{file}`, which is not imported here"
+                        ))
+                        .with_span(&o.span)
+                        .with_note(format!("import it with `import {{ {r} }} from \"{file}\"`")),
+                    );
+                    // linked anyway so that it is not reported as undefined as well
+                    *r = key(defined_in, r);
+                }
+            })
+            .visit_object(&mut o.value);
+            objects.push(o);
+        }
+    }
+
+    (objects, diagnostics)
+}
+
+struct Module {
+    /// Path of the file relative to the directory of the first input file.
+    file: String,
+    /// Prefix of the component keys of definitions that have to be qualified: the path
+    /// relative to `root_dir`, or for files outside of it the namespace they are imported
+    /// as, falling back to the file name.
+    qualifier: String,
+    names: Vec<String>,
+}
+
+impl Module {
+    fn new(file: &SourceFile, root_dir: &Path, alias: Option<&str>) -> Self {
+        let path = Path::new(&file.path);
+        let qualifier = match (path.strip_prefix(root_dir), alias) {
+            (Ok(relative), _) => relative
+                .with_extension("")
+                .iter()
+                .map(|c| c.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("."),
+            (Err(_), Some(alias)) => alias.to_string(),
+            (Err(_), None) => path
+                .file_stem()
+                .unwrap_or(path.as_os_str())
+                .to_string_lossy()
+                .to_string(),
+        };
+
+        Module {
+            qualifier,
+            file: relative_path(path, root_dir),
+            names: file
+                .objects
+                .iter()
+                .filter_map(|o| defined_name(&o.value).map(|(name, _)| name.to_string()))
+                .collect(),
+        }
+    }
+}
+
+/// Names visible in one file besides the global ones, mapped to component keys.
+#[derive(Default)]
+struct Scope {
+    own: HashMap<String, String>,
+    selected: HashMap<String, String>,
+    namespaces: HashMap<String, HashMap<String, String>>,
+}
+
+impl Scope {
+    fn get(&self, name: &str) -> Option<&String> {
+        if let Some((ns, name)) = name.split_once('.') {
+            return self.namespaces.get(ns)?.get(name);
+        }
+        self.own.get(name).or_else(|| self.selected.get(name))
+    }
+}
+
+fn defined_name_mut(object: &mut Object) -> Option<&mut String> {
+    match object {
+        Object::Schema(s) => s.name.as_mut(),
+        Object::Enum(e) => Some(&mut e.name),
+        Object::Tag(t) => Some(&mut t.name),
+        Object::Response(r) => Some(&mut r.name),
+        Object::RequestBody(r) => Some(&mut r.name),
+        Object::Constant(c) => Some(&mut c.name),
+        Object::Server(s) => Some(&mut s.name),
+        Object::SecurityScheme(s) => Some(&mut s.name),
+        Object::Path(_) | Object::Info(_) => None,
+    }
+}
+
+/// Calls the closure with every name in an object that refers to a definition.
+struct References<F>(F);
+
+impl<F: FnMut(&mut String)> VisitMut for References<F> {
+    fn visit_attributes(&mut self, attrs: &mut Attributes) {
+        for (key, v) in attrs.iter_mut() {
+            if key != SECURITY_KEY {
+                self.visit_value(v);
+                continue;
+            }
+            // security requirements name the schemes by the keys of their objects
+            match v {
+                Value::List(items) => {
+                    for item in items {
+                        match item {
+                            Value::Object(entries) => {
+                                for (name, v) in entries {
+                                    (self.0)(name);
+                                    self.visit_value(v);
+                                }
+                            }
+                            item => self.visit_value(item),
+                        }
+                    }
+                }
+                v => self.visit_value(v),
+            }
+        }
+    }
+
+    fn visit_reference(&mut self, name: &mut String, _kind: ReferenceKind) {
+        (self.0)(name)
+    }
+}
@@ -1,13 +1,10 @@
-use crate::converter::generate;
-use clap::Parser;
-use log::debug;
-use parser::{parse, SourceFileContent};
-use std::collections::HashSet;
-use std::fs::{read_to_string, File};
+use clap::{Parser, ValueEnum};
+use openapi_gen::openapi::OpenApi;
+use openapi_gen::{Diagnostic, Error, Generator};
+use std::fs::File;
+use std::io::{stdout, BufWriter, Write};
 use std::path::Path;
-
-mod converter;
-mod openapi;
+use std::process::ExitCode;
 
 #[derive(Debug, Parser)]
 #[command(version, about, long_about = None)]
@@ -15,73 +12,200 @@ struct Args {
     #[arg(help = "Input source file")]
     input: String,
 
-    #[arg(short, long, help = "Include search directory")]
+    #[arg(
+        short,
+        long,
+        help = "Directory searched for imported files, after the directory of the importing file"
+    )]
     include: Vec<String>,
 
-    #[arg(short, long, default_value = "openapi.yml", help = "Output file")]
+    #[arg(
+        short,
+        long,
+        default_value = "openapi.yml",
+        help = "Output file, or `-` for standard output"
+    )]
     output: String,
 
+    #[arg(
+        short,
+        long,
+        value_enum,
+        help = "Output format [default: inferred from the output file extension, or yaml]"
+    )]
+    format: Option<Format>,
+
+    #[arg(long, help = "Write JSON on a single line instead of indenting it")]
+    compact: bool,
+
     #[arg(short, long, help = "Info config name to use instead of default info")]
     config: Option<String>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        default_missing_value = "tree",
+        help = "Print the import graph of the input file instead of generating output"
+    )]
+    print_imports: Option<GraphFormat>,
+
+    #[arg(
+        long,
+        help = "Sort paths, components and properties alphabetically instead of keeping the order of declaration"
+    )]
+    sort: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "all-of",
+        help = "How schemas that extend another are written"
+    )]
+    inheritance: Inheritance,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "concat",
+        help = "How instances of generic schemas like `Page<User>` are named"
+    )]
+    generic_naming: GenericNaming,
 }
 
-fn main() {
-    env_logger::init();
-    let args = Args::parse();
+#[derive(Debug, Copy, Clone, ValueEnum)]
+enum Format {
+    Yaml,
+    Json,
+}
 
-    let objects = {
-        let mut objects = Vec::new();
-
-        let mut loaded_files = HashSet::new();
-        let mut imported_but_unread = {
-            let mut v = Vec::new();
-            v.push(args.input.to_string());
-            v
-        };
-
-        loop {
-            let file = match imported_but_unread.pop() {
-                Some(f) => f,
-                None => break,
-            };
-            if loaded_files.contains(&file) {
-                continue;
-            }
-
-            let (file, file_content) = load_and_parse(file.as_str());
-            let parent = Path::new(file.as_str()).parent().unwrap();
-
-            loaded_files.insert(file.clone());
-            objects.extend_from_slice(file_content.objects.as_slice());
-
-            imported_but_unread.extend(
-                file_content
-                    .imports
-                    .into_iter()
-                    .map(|i| parent.join(i.file).to_str().unwrap().to_string()),
-            );
+#[derive(Debug, Copy, Clone, ValueEnum)]
+enum GraphFormat {
+    Tree,
+    Dot,
+}
+
+#[derive(Debug, Copy, Clone, ValueEnum)]
+enum Inheritance {
+    /// `allOf` a reference to the base schema and the added fields
+    AllOf,
+    /// A single object with the fields of the base schemas copied in
+    Flatten,
+}
+
+#[derive(Debug, Copy, Clone, ValueEnum)]
+enum GenericNaming {
+    /// `PageUser`, `ResultUserError`
+    Concat,
+    /// `Page_User`, `Result_User_Error`
+    Underscore,
+    /// `PageOfUser`, `ResultOfUserAndError`
+    Of,
+}
+
+impl From<GenericNaming> for openapi_gen::GenericNaming {
+    fn from(value: GenericNaming) -> Self {
+        match value {
+            GenericNaming::Concat => openapi_gen::GenericNaming::Concat,
+            GenericNaming::Underscore => openapi_gen::GenericNaming::Underscore,
+            GenericNaming::Of => openapi_gen::GenericNaming::Of,
         }
+    }
+}
 
-        objects
-    };
+impl From<Inheritance> for openapi_gen::Inheritance {
+    fn from(value: Inheritance) -> Self {
+        match value {
+            Inheritance::AllOf => openapi_gen::Inheritance::AllOf,
+            Inheritance::Flatten => openapi_gen::Inheritance::Flatten,
+        }
+    }
+}
 
-    let openapi = generate(objects, args.config);
+impl Format {
+    fn from_extension(output: &str) -> Format {
+        match Path::new(output).extension().and_then(|e| e.to_str()) {
+            Some("json") => Format::Json,
+            _ => Format::Yaml,
+        }
+    }
 
-    let file = File::create(args.output.as_str()).unwrap();
-    serde_yaml::to_writer(file, &openapi).unwrap();
+    fn document_format(self, compact: bool) -> openapi_gen::Format {
+        match self {
+            Format::Yaml => openapi_gen::Format::Yaml,
+            Format::Json if compact => openapi_gen::Format::CompactJson,
+            Format::Json => openapi_gen::Format::Json,
+        }
+    }
 }
 
-fn load_and_parse(file: &str) -> (String, SourceFileContent) {
-    let file = Path::new(file)
-        .canonicalize()
-        .unwrap()
-        .to_str()
-        .unwrap()
-        .to_string();
-    let content = read_to_string(file.as_str()).unwrap();
-    debug!(
-        "load imported file: {file} ({} bytes)",
-        content.as_bytes().len()
-    );
-    (file, parse(content.as_str()).unwrap())
+fn main() -> ExitCode {
+    env_logger::init();
+    let args = Args::parse();
+
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprint!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), Error> {
+    let mut generator = Generator::new()
+        .source_file(&args.input)
+        .config(args.config)
+        .sort(args.sort)
+        .inheritance(args.inheritance.into())
+        .generic_naming(args.generic_naming.into());
+    for dir in &args.include {
+        generator = generator.include_dir(dir);
+    }
+    let generator = generator.include_dirs_from_env();
+
+    if let Some(format) = args.print_imports {
+        let graph = generator.import_graph()?;
+        eprint!("{}", graph.render_warnings());
+        match format {
+            GraphFormat::Tree => print!("{}", graph.value.to_tree()),
+            GraphFormat::Dot => print!("{}", graph.value.to_dot()),
+        }
+        return Ok(());
+    }
+
+    let generated = generator.generate()?;
+    eprint!("{}", generated.render_warnings());
+
+    let format = args
+        .format
+        .unwrap_or_else(|| Format::from_extension(&args.output));
+    write_output(
+        &generated.value,
+        &args.output,
+        format.document_format(args.compact),
+    )?;
+    Ok(())
+}
+
+fn write_output(
+    openapi: &OpenApi,
+    output: &str,
+    format: openapi_gen::Format,
+) -> Result<(), Diagnostic> {
+    let (writer, target): (Box<dyn Write>, String) = if output == "-" {
+        (Box::new(stdout().lock()), "standard output".to_string())
+    } else {
+        let file = File::create(output)
+            .map_err(|e| Diagnostic::error(format!("cannot create output file `{output}`: {e}")))?;
+        (
+            Box::new(BufWriter::new(file)),
+            format!("output file `{output}`"),
+        )
+    };
+
+    openapi
+        .write(writer, format)
+        .map_err(|e| Diagnostic::error(format!("cannot write {target}: {e}")))
 }
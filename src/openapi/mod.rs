@@ -1,6 +1,7 @@
+use indexmap::IndexMap;
 use serde::Serialize;
-use std::collections::HashMap;
 use std::fmt::Debug;
+use std::io::{self, Write};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct OpenApi {
@@ -11,14 +12,69 @@ pub struct OpenApi {
     pub paths: Paths,
     pub components: Components,
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub security: Vec<SecurityRequirement>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<Tag>,
 }
 
-pub type Attributes = HashMap<String, serde_json::Value>;
+impl OpenApi {
+    /// Sorts the paths, components, properties, response codes and tags by name. Attributes
+    /// keep the order they are written in.
+    pub fn sort(&mut self) {
+        self.paths.content.sort_keys();
+        for path in self.paths.content.values_mut() {
+            path.sort();
+        }
+        self.components.sort();
+        self.tags.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    /// Writes the document to `writer` in `format`.
+    pub fn write(&self, mut writer: impl Write, format: Format) -> io::Result<()> {
+        match format {
+            Format::Yaml => serde_yaml::to_writer(&mut writer, self).map_err(io::Error::other)?,
+            Format::Json => {
+                serde_json::to_writer_pretty(&mut writer, self)?;
+                writeln!(writer)?;
+            }
+            Format::CompactJson => {
+                serde_json::to_writer(&mut writer, self)?;
+                writeln!(writer)?;
+            }
+        }
+        writer.flush()
+    }
+
+    /// Serializes the document in `format`.
+    pub fn to_bytes(&self, format: Format) -> io::Result<Vec<u8>> {
+        let mut bytes = vec![];
+        self.write(&mut bytes, format)?;
+        Ok(bytes)
+    }
+}
+
+/// Serialization format of the document.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    Yaml,
+    /// Indented JSON.
+    Json,
+    /// JSON on a single line.
+    CompactJson,
+}
+
+/// Parts of the document that can be sorted by name.
+trait Sort {
+    fn sort(&mut self);
+}
+
+pub type Attributes = IndexMap<String, serde_json::Value>;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct Info {
     pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
     pub version: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -36,25 +92,54 @@ pub struct Server {
 #[derive(Debug, Clone, Serialize)]
 pub struct Paths {
     #[serde(flatten)]
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
-    pub content: HashMap<String, Path>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub content: IndexMap<String, Path>,
 }
 
 #[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Components {
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
-    pub schemas: HashMap<String, ReferenceOr<Schema>>,
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
-    pub responses: HashMap<String, Response>,
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
-    pub parameters: HashMap<String, Parameter>,
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
-    pub request_bodies: HashMap<String, ReferenceOr<RequestBody>>,
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
-    pub headers: HashMap<String, Parameter>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub schemas: IndexMap<String, ReferenceOr<Schema>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub responses: IndexMap<String, Response>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub parameters: IndexMap<String, Parameter>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub request_bodies: IndexMap<String, ReferenceOr<RequestBody>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub headers: IndexMap<String, Parameter>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub security_schemes: IndexMap<String, SecurityScheme>,
+}
+
+impl Sort for Components {
+    fn sort(&mut self) {
+        self.schemas.sort_keys();
+        self.schemas.values_mut().for_each(ReferenceOr::sort);
+        self.responses.sort_keys();
+        self.responses.values_mut().for_each(Response::sort);
+        self.parameters.sort_keys();
+        self.parameters.values_mut().for_each(Parameter::sort);
+        self.request_bodies.sort_keys();
+        self.request_bodies.values_mut().for_each(ReferenceOr::sort);
+        self.headers.sort_keys();
+        self.headers.values_mut().for_each(Parameter::sort);
+        self.security_schemes.sort_keys();
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SecurityScheme {
+    #[serde(rename = "type")]
+    pub scheme_type: String,
+    #[serde(flatten)]
+    pub attributes: Attributes,
 }
 
+/// Names of the security schemes required together, with the scopes each needs.
+pub type SecurityRequirement = IndexMap<String, Vec<String>>;
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Tag {
     pub name: String,
@@ -64,6 +149,8 @@ pub struct Tag {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct Path {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub parameters: Vec<ReferenceOr<Parameter>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -84,6 +171,27 @@ pub struct Path {
     pub trace: Option<Operation>,
 }
 
+impl Sort for Path {
+    fn sort(&mut self) {
+        self.parameters.iter_mut().for_each(ReferenceOr::sort);
+        for op in [
+            &mut self.get,
+            &mut self.post,
+            &mut self.put,
+            &mut self.delete,
+            &mut self.options,
+            &mut self.head,
+            &mut self.patch,
+            &mut self.trace,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            op.sort();
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Operation {
@@ -93,19 +201,32 @@ pub struct Operation {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub parameters: Vec<ReferenceOr<Parameter>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub request_body: Option<RequestBody>,
+    pub request_body: Option<ReferenceOr<RequestBody>>,
     pub responses: Responses,
     #[serde(flatten)]
     pub attributes: Attributes,
 }
 
+impl Sort for Operation {
+    fn sort(&mut self) {
+        self.parameters.iter_mut().for_each(ReferenceOr::sort);
+        self.request_body.iter_mut().for_each(ReferenceOr::sort);
+        self.responses
+            .default
+            .iter_mut()
+            .for_each(ReferenceOr::sort);
+        self.responses.code.sort_keys();
+        self.responses.code.values_mut().for_each(ReferenceOr::sort);
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Responses {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<ReferenceOr<Response>>,
     #[serde(flatten)]
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
-    pub code: HashMap<String, ReferenceOr<Response>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub code: IndexMap<String, ReferenceOr<Response>>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -125,8 +246,8 @@ pub enum Schema {
     Object {
         #[serde(skip_serializing_if = "Vec::is_empty")]
         required: Vec<String>,
-        #[serde(skip_serializing_if = "HashMap::is_empty")]
-        properties: HashMap<String, ReferenceOr<Schema>>,
+        #[serde(skip_serializing_if = "IndexMap::is_empty")]
+        properties: IndexMap<String, ReferenceOr<Schema>>,
         #[serde(flatten)]
         attributes: Attributes,
     },
@@ -136,6 +257,12 @@ pub enum Schema {
         #[serde(flatten)]
         attributes: Attributes,
     },
+    Number {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        format: Option<String>,
+        #[serde(flatten)]
+        attributes: Attributes,
+    },
     Boolean {
         #[serde(flatten)]
         attributes: Attributes,
@@ -145,18 +272,103 @@ pub enum Schema {
         #[serde(flatten)]
         attributes: Attributes,
     },
+    /// Composed schemas have no `type` of their own.
+    #[serde(untagged)]
+    OneOf {
+        #[serde(rename = "oneOf")]
+        one_of: Vec<ReferenceOr<Schema>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        discriminator: Option<Discriminator>,
+        #[serde(flatten)]
+        attributes: Attributes,
+    },
+    #[serde(untagged)]
+    AnyOf {
+        #[serde(rename = "anyOf")]
+        any_of: Vec<ReferenceOr<Schema>>,
+        #[serde(flatten)]
+        attributes: Attributes,
+    },
+    #[serde(untagged)]
+    AllOf {
+        #[serde(rename = "allOf")]
+        all_of: Vec<ReferenceOr<Schema>>,
+        #[serde(flatten)]
+        attributes: Attributes,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Discriminator {
+    pub property_name: String,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub mapping: IndexMap<String, String>,
+}
+
+impl Schema {
+    pub fn attributes_mut(&mut self) -> &mut Attributes {
+        match self {
+            Schema::String { attributes, .. }
+            | Schema::Object { attributes, .. }
+            | Schema::Integer { attributes, .. }
+            | Schema::Number { attributes, .. }
+            | Schema::Boolean { attributes }
+            | Schema::Array { attributes, .. }
+            | Schema::OneOf { attributes, .. }
+            | Schema::AnyOf { attributes, .. }
+            | Schema::AllOf { attributes, .. } => attributes,
+        }
+    }
+}
+
+impl Sort for Schema {
+    fn sort(&mut self) {
+        match self {
+            Schema::Object { properties, .. } => {
+                properties.sort_keys();
+                properties.values_mut().for_each(ReferenceOr::sort);
+            }
+            Schema::Array { items, .. } => items.sort(),
+            Schema::OneOf {
+                one_of,
+                discriminator,
+                ..
+            } => {
+                one_of.iter_mut().for_each(ReferenceOr::sort);
+                if let Some(d) = discriminator {
+                    d.mapping.sort_keys();
+                }
+            }
+            Schema::AnyOf {
+                any_of: schemas, ..
+            }
+            | Schema::AllOf {
+                all_of: schemas, ..
+            } => schemas.iter_mut().for_each(ReferenceOr::sort),
+            _ => {}
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct Response {
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
-    pub headers: HashMap<String, ReferenceOr<Parameter>>,
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
-    pub content: HashMap<String, MediaType>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub headers: IndexMap<String, ReferenceOr<Parameter>>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub content: IndexMap<String, MediaType>,
     #[serde(flatten)]
     pub attributes: Attributes,
 }
 
+impl Sort for Response {
+    fn sort(&mut self) {
+        self.headers.sort_keys();
+        self.headers.values_mut().for_each(ReferenceOr::sort);
+        self.content.values_mut().for_each(MediaType::sort);
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Parameter {
     pub name: String,
@@ -168,6 +380,12 @@ pub struct Parameter {
     pub attributes: Attributes,
 }
 
+impl Sort for Parameter {
+    fn sort(&mut self) {
+        self.schema.sort();
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
 pub enum ReferenceOr<T>
@@ -177,10 +395,23 @@ where
     Ref {
         #[serde(rename = "$ref")]
         ref_path: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
     },
     Value(T),
 }
 
+impl<T> Sort for ReferenceOr<T>
+where
+    T: Debug + Clone + Serialize + Sort,
+{
+    fn sort(&mut self) {
+        if let ReferenceOr::Value(v) = self {
+            v.sort();
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ParameterIn {
@@ -192,13 +423,25 @@ pub enum ParameterIn {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct RequestBody {
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
-    pub content: HashMap<String, MediaType>,
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub content: IndexMap<String, MediaType>,
     #[serde(flatten)]
     pub attributes: Attributes,
 }
 
+impl Sort for RequestBody {
+    fn sort(&mut self) {
+        self.content.values_mut().for_each(MediaType::sort);
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct MediaType {
     pub schema: ReferenceOr<Schema>,
 }
+
+impl Sort for MediaType {
+    fn sort(&mut self) {
+        self.schema.sort();
+    }
+}
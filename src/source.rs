@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Where a [`Generator`](crate::Generator) reads source files from.
+pub trait SourceProvider {
+    /// Returns the canonical path of the source file at `path`, which names the file in
+    /// diagnostics and tells whether two imports load the same file.
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf>;
+
+    /// Reads the source file at a path returned by [`SourceProvider::canonicalize`].
+    fn read(&self, path: &Path) -> io::Result<String>;
+}
+
+/// Reads source files from the file system.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct DiskProvider;
+
+impl SourceProvider for DiskProvider {
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        let path = path.canonicalize()?;
+        if path.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::IsADirectory,
+                "is a directory",
+            ));
+        }
+        Ok(path)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<String> {
+        read_to_string(path)
+    }
+}
+
+/// Serves source files from memory, such as unsaved editor buffers or strings embedded in
+/// tests, and optionally falls back to another provider for the files it does not hold.
+#[derive(Default)]
+pub struct MemoryProvider {
+    files: HashMap<PathBuf, String>,
+    fallback: Option<Box<dyn SourceProvider>>,
+}
+
+impl MemoryProvider {
+    pub fn new() -> Self {
+        MemoryProvider::default()
+    }
+
+    /// Adds or replaces the file at `path`.
+    pub fn insert(&mut self, path: impl AsRef<Path>, content: impl Into<String>) {
+        self.files.insert(normalize(path.as_ref()), content.into());
+    }
+
+    /// Adds or replaces the file at `path`.
+    pub fn with_file(mut self, path: impl AsRef<Path>, content: impl Into<String>) -> Self {
+        self.insert(path, content);
+        self
+    }
+
+    /// Reads the files not held in memory from `provider`.
+    pub fn with_fallback(mut self, provider: impl SourceProvider + 'static) -> Self {
+        self.fallback = Some(Box::new(provider));
+        self
+    }
+}
+
+impl SourceProvider for MemoryProvider {
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        let normalized = normalize(path);
+        if self.files.contains_key(&normalized) {
+            return Ok(normalized);
+        }
+        match &self.fallback {
+            Some(fallback) => fallback.canonicalize(path),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no such file in memory",
+            )),
+        }
+    }
+
+    fn read(&self, path: &Path) -> io::Result<String> {
+        match (self.files.get(path), &self.fallback) {
+            (Some(content), _) => Ok(content.clone()),
+            (None, Some(fallback)) => fallback.read(path),
+            (None, None) => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no such file in memory",
+            )),
+        }
+    }
+}
+
+/// Removes `.` and resolves `..` components of `path` without touching the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for c in path.components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(c),
+            },
+            _ => normalized.push(c),
+        }
+    }
+    normalized
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::project;
+
+const SOURCE: &str = r#"
+path "/health" {
+    get health() {
+        return 200 response { content = String }
+    }
+}
+"#;
+
+#[test]
+fn format_is_inferred_from_the_output_extension() {
+    let project = project(SOURCE);
+    assert!(project.run(&["main.oai", "-o", "openapi.json"]).success);
+    let json: serde_json::Value = serde_json::from_str(&project.read("openapi.json")).unwrap();
+    assert_eq!(json["info"]["title"], "API");
+
+    assert!(project.run(&["main.oai", "-o", "openapi.yaml"]).success);
+    assert!(project.read("openapi.yaml").starts_with("openapi: 3.1.0\n"));
+}
+
+#[test]
+fn format_overrides_the_extension() {
+    let project = project(SOURCE);
+    assert!(
+        project
+            .run(&["main.oai", "-o", "openapi.yml", "--format", "json"])
+            .success
+    );
+    assert!(project.read("openapi.yml").starts_with("{\n"));
+}
+
+#[test]
+fn compact_json_is_one_line() {
+    let project = project(SOURCE);
+    assert!(
+        project
+            .run(&["main.oai", "-o", "openapi.json", "--compact"])
+            .success
+    );
+    assert_eq!(project.read("openapi.json").trim_end().lines().count(), 1);
+}
+
+#[test]
+fn dash_writes_to_stdout() {
+    let project = project(SOURCE);
+    let run = project.run(&["main.oai", "-o", "-", "-f", "json"]);
+    assert!(run.success, "{}", run.stderr);
+    let json: serde_json::Value = serde_json::from_str(&run.stdout).unwrap();
+    assert_eq!(json["paths"]["/health"]["get"]["operationId"], "health");
+    assert!(!project.dir.join("-").exists());
+}
+
+#[test]
+fn errors_fail_the_run() {
+    let project = project("schema User { id Int64 }");
+    let run = project.run(&["main.oai"]);
+    assert!(!run.success);
+    assert!(run.stderr.starts_with("error: "), "{}", run.stderr);
+    assert!(!project.dir.join("openapi.yml").exists());
+}
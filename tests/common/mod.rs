@@ -0,0 +1,180 @@
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Temporary directory of source files to run `openapi-gen` in, removed when dropped.
+pub struct Project {
+    pub dir: PathBuf,
+}
+
+/// What a run of `openapi-gen` printed.
+pub struct Run {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Error or warning printed by `openapi-gen`.
+#[derive(Debug)]
+pub struct Reported {
+    pub error: bool,
+    pub message: String,
+    /// Line the diagnostic points at, if it points into a file.
+    pub line: Option<usize>,
+}
+
+impl Project {
+    pub fn new() -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "openapi-gen-test-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+
+        Project {
+            dir: dir.canonicalize().unwrap(),
+        }
+    }
+
+    /// Writes `content` to `path`, relative to the project directory.
+    pub fn file(self, path: &str, content: &str) -> Self {
+        let path = self.dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+        self
+    }
+
+    pub fn read(&self, path: &str) -> String {
+        fs::read_to_string(self.dir.join(path)).unwrap()
+    }
+
+    /// `openapi-gen` with `args`, run in the project directory.
+    pub fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_openapi-gen"));
+        command
+            .args(args)
+            .current_dir(&self.dir)
+            .env_remove("OPENAPI_GEN_PATH");
+        command
+    }
+
+    pub fn run(&self, args: &[&str]) -> Run {
+        run(self.command(args))
+    }
+
+    /// The document generated from `main.oai` with `args`, as JSON.
+    pub fn generate(&self, args: &[&str]) -> serde_json::Value {
+        self.generate_from("main.oai", args)
+    }
+
+    pub fn generate_from(&self, input: &str, args: &[&str]) -> serde_json::Value {
+        let run = self.run(&[&[input, "-o", "openapi.yml"], args].concat());
+        assert!(run.success, "{}", run.stderr);
+        serde_yaml::from_str(&self.read("openapi.yml")).unwrap()
+    }
+
+    /// The errors and warnings reported for `main.oai` with `args`, whether it generates or
+    /// not.
+    pub fn diagnostics(&self, args: &[&str]) -> Vec<Reported> {
+        self.diagnostics_from("main.oai", args)
+    }
+
+    pub fn diagnostics_from(&self, input: &str, args: &[&str]) -> Vec<Reported> {
+        let run = self.run(&[&[input, "-o", "openapi.yml"], args].concat());
+        reported(&run.stderr)
+    }
+}
+
+impl Drop for Project {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+pub fn run(mut command: Command) -> Run {
+    let output = command.output().unwrap();
+    Run {
+        success: output.status.success(),
+        stdout: String::from_utf8(output.stdout).unwrap(),
+        stderr: String::from_utf8(output.stderr).unwrap(),
+    }
+}
+
+/// Project with `source` as `main.oai`, followed by a default info config so that the lines
+/// of `source` keep their numbers.
+pub fn project(source: &str) -> Project {
+    Project::new().file(
+        "main.oai",
+        &format!(
+            "{source}\ndefault info Api {{\n    title = \"API\"\n    version = \"1.0.0\"\n}}\n"
+        ),
+    )
+}
+
+/// The document generated from `source`, as JSON.
+pub fn json(source: &str) -> serde_json::Value {
+    project(source).generate(&[])
+}
+
+pub fn diagnostics(source: &str) -> Vec<Reported> {
+    project(source).diagnostics(&[])
+}
+
+pub fn errors(source: &str) -> Vec<String> {
+    messages(diagnostics(source), true)
+}
+
+pub fn warnings(source: &str) -> Vec<String> {
+    messages(diagnostics(source), false)
+}
+
+fn messages(diagnostics: Vec<Reported>, error: bool) -> Vec<String> {
+    diagnostics
+        .into_iter()
+        .filter(|d| d.error == error && !d.message.starts_with("aborting"))
+        .map(|d| d.message)
+        .collect()
+}
+
+/// Line of the diagnostic with `message`.
+pub fn line_of(diagnostics: &[Reported], message: &str) -> usize {
+    diagnostics
+        .iter()
+        .find(|d| d.message == message)
+        .unwrap_or_else(|| panic!("no diagnostic `{message}` in {diagnostics:#?}"))
+        .line
+        .expect("diagnostic without a span")
+}
+
+/// The diagnostics in `stderr`, each starting with `error: ` or `warning: ` and pointing into a
+/// file with a ` --> file:line:column` line.
+fn reported(stderr: &str) -> Vec<Reported> {
+    let mut diagnostics = Vec::<Reported>::new();
+    for line in stderr.lines() {
+        if let Some(message) = line.strip_prefix("error: ") {
+            diagnostics.push(Reported {
+                error: true,
+                message: message.to_string(),
+                line: None,
+            });
+        } else if let Some(message) = line.strip_prefix("warning: ") {
+            diagnostics.push(Reported {
+                error: false,
+                message: message.to_string(),
+                line: None,
+            });
+        } else if let Some(location) = line.trim_start().strip_prefix("--> ") {
+            let mut parts = location.rsplitn(3, ':');
+            let _column = parts.next();
+            if let Some(d) = diagnostics.last_mut() {
+                d.line = parts.next().and_then(|l| l.parse().ok());
+            }
+        }
+    }
+    diagnostics
+}
@@ -0,0 +1,309 @@
+mod common;
+
+use common::{diagnostics, errors, line_of, warnings, Project};
+
+#[test]
+fn syntax_error_points_at_the_line() {
+    let d = diagnostics(
+        r#"
+schema User {
+    id: Int64,
+    name String,
+}
+"#,
+    );
+    assert!(d[0].error, "{d:#?}");
+    assert_eq!(d[0].line, Some(4));
+}
+
+#[test]
+fn duplicate_operation_is_a_syntax_error() {
+    let source = r#"
+path "/users" {
+    get first() {
+        return 200 response { content = String }
+    }
+    get second() {
+        return 200 response { content = String }
+    }
+}
+"#;
+    let d = diagnostics(source);
+    assert_eq!(
+        line_of(&d, "duplicate `get` operation for path \"/users\""),
+        6
+    );
+}
+
+#[test]
+fn duplicate_response_is_a_syntax_error() {
+    let source = r#"
+path "/users" {
+    patch updateUsers() {
+        return 200 response { content = String }
+        return 200 response { content = Int }
+    }
+}
+"#;
+    let d = diagnostics(source);
+    assert_eq!(
+        line_of(&d, "duplicate `200` response for operation `updateUsers`"),
+        5
+    );
+}
+
+#[test]
+fn undefined_reference_points_at_the_field() {
+    let source = r#"
+schema User {
+    id: Int64,
+    group: Group,
+}
+path "/users" {
+    get getUser() {
+        return 200 response { content = User }
+    }
+}
+"#;
+    let d = diagnostics(source);
+    assert_eq!(line_of(&d, "schema `Group` is not defined"), 4);
+}
+
+#[test]
+fn wrong_kind_points_at_the_operation_and_counts_as_a_use() {
+    let source = r#"
+schema User { id: Int64 }
+path "/users" {
+    get getUser() {
+        return 200 User
+    }
+}
+"#;
+    let d = diagnostics(source);
+    assert_eq!(line_of(&d, "`User` is a schema, not a response"), 4);
+    assert!(
+        !d.iter().any(|d| d.message.contains("never used")),
+        "{d:#?}"
+    );
+}
+
+#[test]
+fn unused_definitions_are_warned_about() {
+    let source = r#"
+schema Unused { id: Int64 }
+path "/health" {
+    get health() {
+        return 200 response { content = String }
+    }
+}
+"#;
+    assert_eq!(warnings(source), ["schema `Unused` is never used"]);
+}
+
+#[test]
+fn tags_must_name_tags() {
+    let source = r#"
+schema User { id: Int64 }
+path "/users" with tag = User {
+    get getUser() {
+        return 200 response { content = User }
+    }
+}
+path "/groups" {
+    get getGroups() with tags = [User] {
+        return 200 response { content = User }
+    }
+}
+"#;
+    let d = diagnostics(source);
+    let lines = d
+        .iter()
+        .filter(|d| d.message == "`User` is a schema, not a tag")
+        .map(|d| d.line)
+        .collect::<Vec<_>>();
+    assert_eq!(lines, [Some(3), Some(9)]);
+}
+
+const USER_PATH: &str = r#"
+path "/users/{id}/posts/{post}" {
+    pathParameters = parameters {
+        id: pathParameter { content = String }
+    }
+
+    get getPost(pathParameters = parameters {
+        post: pathParameter { content = optional String },
+        other: pathParameter { content = String }
+    }) {
+        return 200 response { content = String }
+    }
+
+    delete deletePosts() {
+        return 204 response { content = String }
+    }
+}
+"#;
+
+#[test]
+fn path_parameters_are_checked_against_the_template() {
+    let d = diagnostics(USER_PATH);
+    assert_eq!(line_of(&d, "path parameter `post` must be required"), 8);
+    assert_eq!(
+        line_of(
+            &d,
+            "path parameter `other` does not appear in `/users/{id}/posts/{post}`"
+        ),
+        9
+    );
+    assert_eq!(
+        line_of(
+            &d,
+            "operation `deletePosts` does not declare path parameter `post`"
+        ),
+        14
+    );
+}
+
+#[test]
+fn repeated_server_entry_is_a_syntax_error() {
+    let source = r#"
+server Production {
+    url = "https://example.com"
+    description = "Production"
+    url = "https://example.org"
+}
+"#;
+    let d = diagnostics(source);
+    assert_eq!(
+        line_of(&d, "`url` is set more than once in server `Production`"),
+        5
+    );
+}
+
+#[test]
+fn duplicate_info_entry_is_a_syntax_error() {
+    let d = Project::new()
+        .file(
+            "main.oai",
+            r#"
+default info Api {
+    title = "API"
+    version = "1.0.0"
+    title = "Again"
+}
+"#,
+        )
+        .diagnostics(&[]);
+    assert_eq!(
+        line_of(&d, "`title` is set more than once in info `Api`"),
+        5
+    );
+}
+
+#[test]
+fn security_requirements_must_name_security_schemes() {
+    let source = r#"
+security ApiKey = apiKey { name = "key"; in = "header" }
+schema User { id: Int64 }
+path "/users" {
+    get getUsers() with security = [User] {
+        return 200 response { content = User }
+    }
+    put putUsers() with security = [{ ApiKey: [], Missing: [] }] {
+        return 200 response { content = User }
+    }
+    delete deleteUsers() with security = ["ApiKey"] {
+        return 200 response { content = User }
+    }
+}
+"#;
+    let d = diagnostics(source);
+    assert_eq!(line_of(&d, "`User` is a schema, not a security scheme"), 5);
+    assert_eq!(line_of(&d, "security scheme `Missing` is not defined"), 8);
+    assert_eq!(
+        line_of(
+            &d,
+            "a security requirement must be the name of a security scheme or an object mapping names to scopes"
+        ),
+        11
+    );
+}
+
+const PAYMENT: &str = r#"
+path "/payments" {
+    get getPayment() {
+        return 200 response { content = Payment }
+    }
+}
+"#;
+
+#[test]
+fn discriminator_property_is_checked_on_every_variant() {
+    let source = format!(
+        r#"
+schema Card {{ kind: String }}
+schema Bank {{ kind: optional String }}
+schema Cash {{ amount: Int }}
+schema Payment = oneOf by "kind" {{ card: Card, bank: Bank, cash: Cash, card: Card }}
+{PAYMENT}"#
+    );
+    assert_eq!(
+        errors(&source),
+        [
+            "discriminator property `kind` of schema `Bank` is optional",
+            "schema `Cash` does not have the discriminator property `kind`",
+            "discriminator value `card` is mapped more than once",
+        ]
+    );
+}
+
+#[test]
+fn extends_cycle_is_an_error() {
+    let source = format!(
+        r#"
+schema A extends B {{ a: String }}
+schema B extends A {{ b: String }}
+schema Payment = A
+{PAYMENT}"#
+    );
+    assert_eq!(errors(&source), ["schema `A` extends itself"]);
+}
+
+#[test]
+fn type_parameters_cannot_be_discriminated() {
+    let source = format!(
+        r#"
+schema Card {{ kind: String }}
+schema Tagged<T> = oneOf by "kind" {{ card: T }}
+schema Payment = Tagged<Card>
+{PAYMENT}"#
+    );
+    assert_eq!(
+        errors(&source),
+        ["type parameter `T` cannot be a variant of a discriminated union"]
+    );
+}
+
+#[test]
+fn generic_arity_is_checked() {
+    let source = format!(
+        r#"
+schema Page<T> {{ items: List<T> }}
+schema Payment = Page<String, Int>
+{PAYMENT}"#
+    );
+    assert_eq!(
+        errors(&source),
+        ["schema `Page` takes 1 type argument, but 2 were given"]
+    );
+}
+
+#[test]
+fn endless_instantiation_is_an_error() {
+    let source = format!(
+        r#"
+schema Nest<T> {{ inner: Nest<List<T>> }}
+schema Payment = Nest<String>
+{PAYMENT}"#
+    );
+    assert_eq!(errors(&source), ["instantiating `Nest` never ends"]);
+}
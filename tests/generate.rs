@@ -0,0 +1,548 @@
+mod common;
+
+use common::{json, project};
+use serde_json::json;
+
+const USERS: &str = r#"
+path "/users" {
+    get listUsers() {
+        return 200 response { content = Users }
+    }
+}
+"#;
+
+#[test]
+fn doc_comments_become_descriptions() {
+    let doc = json(
+        r#"
+/// A user of the service.
+schema User {
+    /// Name shown to others.
+    name: String,
+    //// not documentation
+    age: Int,
+}
+enum Role {
+    /// Can do anything.
+    Admin,
+    Guest,
+}
+path "/users/{id}" {
+    /// Gets a user.
+    ///
+    /// Fails when the user does not exist.
+    get getUser(
+        pathParameters = parameters {
+            /// Id of the user.
+            id: pathParameter { content = String }
+        }
+    ) {
+        return 200 response { content = User }
+    }
+
+    /// Deletes a user.
+    delete deleteUser(
+        pathParameters = parameters { id: pathParameter { content = String } }
+    ) with summary = "Removes a user" {
+        return 200 response { content = Role }
+    }
+}
+"#,
+    );
+
+    let user = &doc["components"]["schemas"]["User"];
+    assert_eq!(user["description"], "A user of the service.");
+    assert_eq!(
+        user["properties"]["name"]["description"],
+        "Name shown to others."
+    );
+    assert!(user["properties"]["age"].get("description").is_none());
+    assert_eq!(
+        doc["components"]["schemas"]["Role"]["x-enum-descriptions"],
+        json!(["Can do anything.", ""])
+    );
+
+    let get = &doc["paths"]["/users/{id}"]["get"];
+    assert_eq!(get["summary"], "Gets a user.");
+    assert_eq!(
+        get["description"],
+        "Gets a user.\n\nFails when the user does not exist."
+    );
+    assert_eq!(get["parameters"][0]["description"], "Id of the user.");
+    let delete = &doc["paths"]["/users/{id}"]["delete"];
+    assert_eq!(delete["summary"], "Removes a user");
+    assert_eq!(delete["description"], "Deletes a user.");
+}
+
+#[test]
+fn request_bodies_are_references_or_inline() {
+    let doc = json(
+        r#"
+requestBody CreateUser {
+    content = schema { name: String }
+}
+path "/users" {
+    post createUser(body = CreateUser) {
+        return 201 response { content = String }
+    }
+    put replaceUser(body = requestBody with description = "The new user" {
+        content = schema { name: String }
+    }) {
+        return 200 response { content = String }
+    }
+}
+"#,
+    );
+
+    let users = &doc["paths"]["/users"];
+    assert_eq!(
+        users["post"]["requestBody"],
+        json!({ "$ref": "#/components/requestBodies/CreateUser" })
+    );
+    let inline = &users["put"]["requestBody"];
+    assert_eq!(inline["description"], "The new user");
+    assert_eq!(
+        inline["content"]["application/json"]["schema"]["properties"]["name"],
+        json!({ "type": "string" })
+    );
+    assert!(doc["components"]["requestBodies"]
+        .as_object()
+        .unwrap()
+        .contains_key("CreateUser"));
+}
+
+#[test]
+fn path_attributes_are_defaults_for_operations() {
+    let doc = json(
+        r#"
+tag User
+tag Admin
+path "/users" with tag = User, deprecated = true {
+    get listUsers() {
+        return 200 response { content = String }
+    }
+    delete deleteUsers() with tags = [User, Admin], deprecated = false {
+        return 204 response { content = String }
+    }
+}
+"#,
+    );
+
+    let users = &doc["paths"]["/users"];
+    assert_eq!(users["get"]["tags"], json!(["User"]));
+    assert_eq!(users["get"]["deprecated"], true);
+    assert_eq!(users["delete"]["tags"], json!(["User", "Admin"]));
+    assert_eq!(users["delete"]["deprecated"], false);
+    assert!(users.get("tags").is_none());
+}
+
+#[test]
+fn patch_operations() {
+    let doc = json(
+        r#"
+path "/users" {
+    patch updateUser() {
+        return 200 response { content = String }
+    }
+}
+"#,
+    );
+
+    assert_eq!(doc["paths"]["/users"]["patch"]["operationId"], "updateUser");
+}
+
+#[test]
+fn number_types_have_formats() {
+    let doc = json(&format!(
+        r#"
+schema Measures {{
+    number: Number,
+    float: Float,
+    float32: Float32,
+    double: Double,
+    float64: Float64 with minimum = 0, maximum = 100,
+}}
+schema Users = List<Measures>
+{USERS}"#
+    ));
+
+    let properties = &doc["components"]["schemas"]["Measures"]["properties"];
+    assert_eq!(properties["number"], json!({ "type": "number" }));
+    assert_eq!(
+        properties["float"],
+        json!({ "type": "number", "format": "float" })
+    );
+    assert_eq!(properties["float32"], properties["float"]);
+    assert_eq!(
+        properties["double"],
+        json!({ "type": "number", "format": "double" })
+    );
+    assert_eq!(
+        properties["float64"],
+        json!({
+            "type": "number",
+            "format": "double",
+            "minimum": 0,
+            "maximum": 100,
+        })
+    );
+}
+
+#[test]
+fn literals_in_attribute_values() {
+    let doc = json(&format!(
+        r#"
+schema Users = List<Int> with example = [0x10, -2, 1.5e1, -.5, true, null, "text"]
+{USERS}"#
+    ));
+
+    assert_eq!(
+        doc["components"]["schemas"]["Users"]["example"],
+        json!([16, -2, 15.0, -0.5, true, null, "text"])
+    );
+}
+
+#[test]
+fn objects_in_attribute_values() {
+    let doc = json(&format!(
+        r#"
+schema User with x-codegen = {{ "class-name": "UserModel", fields: [{{ name: "id" }}] }} {{
+    id: Int,
+}}
+schema Users = List<User>
+{USERS}"#
+    ));
+
+    assert_eq!(
+        doc["components"]["schemas"]["User"]["x-codegen"],
+        json!({ "class-name": "UserModel", "fields": [{ "name": "id" }] })
+    );
+}
+
+#[test]
+fn names_in_attribute_values_are_replaced() {
+    let doc = json(&format!(
+        r#"
+const PageSize = 50
+const Limits = {{ default: PageSize, max: 100 }}
+tag Accounts
+schema User {{ id: Int }}
+schema Users = List<User> with x-limits = Limits, x-tag = Accounts, x-item = User, example = [PageSize]
+{USERS}"#
+    ));
+
+    let users = &doc["components"]["schemas"]["Users"];
+    assert_eq!(users["x-limits"], json!({ "default": 50, "max": 100 }));
+    assert_eq!(users["example"], json!([50]));
+    assert_eq!(users["x-tag"], "Accounts");
+    assert_eq!(
+        users["x-item"],
+        json!({ "$ref": "#/components/schemas/User" })
+    );
+}
+
+#[test]
+fn output_keeps_the_order_of_declaration() {
+    let doc = json(
+        r#"
+schema Zebra { b: Int, a: Int }
+schema Apple { id: Int }
+path "/z" {
+    get z() {
+        return 404 response { content = Zebra }
+        return 200 response { content = Apple }
+    }
+}
+path "/a" {
+    get a() {
+        return 200 response { content = Zebra }
+    }
+}
+"#,
+    );
+
+    let keys = |v: &serde_json::Value| v.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+    assert_eq!(keys(&doc["components"]["schemas"]), ["Zebra", "Apple"]);
+    assert_eq!(
+        keys(&doc["components"]["schemas"]["Zebra"]["properties"]),
+        ["b", "a"]
+    );
+    assert_eq!(keys(&doc["paths"]), ["/z", "/a"]);
+    assert_eq!(
+        keys(&doc["paths"]["/z"]["get"]["responses"]),
+        ["404", "200"]
+    );
+}
+
+#[test]
+fn sort_orders_by_name() {
+    let doc = project(
+        r#"
+schema Zebra { b: Int, a: Int }
+schema Apple { id: Int }
+path "/z" {
+    get z() {
+        return 404 response { content = Zebra }
+        return 200 response { content = Apple }
+    }
+}
+path "/a" {
+    get a() {
+        return 200 response { content = Zebra }
+    }
+}
+"#,
+    )
+    .generate(&["--sort"]);
+
+    let keys = |v: &serde_json::Value| v.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+    assert_eq!(keys(&doc["components"]["schemas"]), ["Apple", "Zebra"]);
+    assert_eq!(
+        keys(&doc["components"]["schemas"]["Zebra"]["properties"]),
+        ["a", "b"]
+    );
+    assert_eq!(keys(&doc["paths"]), ["/a", "/z"]);
+    assert_eq!(
+        keys(&doc["paths"]["/z"]["get"]["responses"]),
+        ["200", "404"]
+    );
+}
+
+const SERVERS: &str = r#"
+/// Production environment.
+server Production {
+    url = "https://{region}.example.com"
+    variables {
+        region = "eu" with enum = ["eu", "us"]
+    }
+}
+server Staging with x-internal = true {
+    url = "https://staging.example.com"
+    description = "Staging"
+}
+path "/uploads" with servers = [Staging] {
+    post upload() with servers = [Production] {
+        return 204 response { content = String }
+    }
+}
+"#;
+
+#[test]
+fn servers_are_listed_in_order() {
+    let doc = json(SERVERS);
+
+    assert_eq!(
+        doc["servers"],
+        json!([
+            {
+                "url": "https://{region}.example.com",
+                "description": "Production environment.",
+                "variables": { "region": { "default": "eu", "enum": ["eu", "us"] } },
+            },
+            {
+                "url": "https://staging.example.com",
+                "description": "Staging",
+                "x-internal": true,
+            },
+        ])
+    );
+    let uploads = &doc["paths"]["/uploads"];
+    assert_eq!(uploads["servers"][0]["url"], "https://staging.example.com");
+    assert_eq!(
+        uploads["post"]["servers"][0]["url"],
+        "https://{region}.example.com"
+    );
+}
+
+#[test]
+fn info_config_selects_servers() {
+    let doc = common::Project::new()
+        .file(
+            "main.oai",
+            &format!(
+                r#"{SERVERS}
+default info Prod {{
+    servers = [Production]
+    version = "2.0.0"
+    summary = "The API"
+    title = "API"
+}}
+info Stg extends Prod {{
+    servers = [Staging]
+}}
+"#
+            ),
+        )
+        .generate(&["--config", "Stg"]);
+
+    assert_eq!(
+        doc["info"],
+        json!({ "title": "API", "summary": "The API", "version": "2.0.0" })
+    );
+    assert_eq!(
+        doc["servers"],
+        json!([{
+            "url": "https://staging.example.com",
+            "description": "Staging",
+            "x-internal": true,
+        }])
+    );
+}
+
+const SECURITY: &str = r#"
+default security Bearer = http { scheme = "bearer"; bearerFormat = "JWT" }
+security OAuth = oauth2 {
+    flows {
+        clientCredentials {
+            tokenUrl = "https://example.com/token"
+            scopes { "read:users" = "Read users" }
+        }
+    }
+}
+"#;
+
+#[test]
+fn security_schemes_and_requirements() {
+    let doc = json(&format!(
+        r#"{SECURITY}
+path "/users" {{
+    get listUsers() with security = [Bearer, {{ OAuth: ["read:users"] }}] {{
+        return 200 response {{ content = String }}
+    }}
+}}
+path "/health" with security = [] {{
+    get health() {{
+        return 200 response {{ content = String }}
+    }}
+}}
+"#
+    ));
+
+    assert_eq!(doc["security"], json!([{ "Bearer": [] }]));
+    assert_eq!(
+        doc["components"]["securitySchemes"]["OAuth"]["flows"]["clientCredentials"]["scopes"],
+        json!({ "read:users": "Read users" })
+    );
+    assert_eq!(
+        doc["paths"]["/users"]["get"]["security"],
+        json!([{ "Bearer": [] }, { "OAuth": ["read:users"] }])
+    );
+    assert_eq!(doc["paths"]["/health"]["get"]["security"], json!([]));
+}
+
+#[test]
+fn composition_follows_precedence() {
+    let doc = json(&format!(
+        r#"
+schema Cat {{ meow: Bool }}
+schema Dog {{ bark: Bool }}
+schema Named {{ name: String }}
+schema Users = List<Cat | Dog & Named | anyOf<Cat, Dog> with description = "Any">
+{USERS}"#
+    ));
+
+    assert_eq!(
+        doc["components"]["schemas"]["Users"]["items"],
+        json!({
+            "oneOf": [
+                { "$ref": "#/components/schemas/Cat" },
+                { "allOf": [
+                    { "$ref": "#/components/schemas/Dog" },
+                    { "$ref": "#/components/schemas/Named" },
+                ] },
+                {
+                    "anyOf": [
+                        { "$ref": "#/components/schemas/Cat" },
+                        { "$ref": "#/components/schemas/Dog" },
+                    ],
+                    "description": "Any",
+                },
+            ]
+        })
+    );
+}
+
+#[test]
+fn discriminated_union_has_a_mapping() {
+    let doc = json(&format!(
+        r#"
+schema Card {{ kind: String, number: String }}
+schema Bank {{ kind: String, iban: String }}
+schema Users = List<oneOf by "kind" {{ card: Card, "bank-transfer": Bank }}>
+{USERS}"#
+    ));
+
+    assert_eq!(
+        doc["components"]["schemas"]["Users"]["items"],
+        json!({
+            "oneOf": [
+                { "$ref": "#/components/schemas/Card" },
+                { "$ref": "#/components/schemas/Bank" },
+            ],
+            "discriminator": {
+                "propertyName": "kind",
+                "mapping": {
+                    "card": "#/components/schemas/Card",
+                    "bank-transfer": "#/components/schemas/Bank",
+                },
+            },
+        })
+    );
+}
+
+const EXTENDS: &str = r#"
+schema User { id: Int64 }
+schema Admin extends User { role: String }
+schema Users = List<Admin>
+"#;
+
+#[test]
+fn extends_is_written_as_all_of() {
+    let doc = json(&format!("{EXTENDS}{USERS}"));
+
+    assert_eq!(
+        doc["components"]["schemas"]["Admin"]["allOf"][0],
+        json!({ "$ref": "#/components/schemas/User" })
+    );
+}
+
+#[test]
+fn extends_can_be_flattened() {
+    let doc = project(&format!("{EXTENDS}{USERS}")).generate(&["--inheritance", "flatten"]);
+
+    let admin = &doc["components"]["schemas"]["Admin"];
+    assert_eq!(admin["required"], json!(["id", "role"]));
+    assert!(admin.get("allOf").is_none());
+}
+
+const GENERICS: &str = r#"
+schema User { id: Int64 }
+schema Page<T> { items: List<T>, next: String }
+schema Users = Page<User>
+"#;
+
+#[test]
+fn generic_schemas_are_instantiated() {
+    let doc = json(&format!("{GENERICS}{USERS}"));
+
+    let schemas = doc["components"]["schemas"].as_object().unwrap();
+    assert!(!schemas.contains_key("Page"));
+    assert_eq!(
+        schemas["PageUser"]["properties"]["items"]["items"],
+        json!({ "$ref": "#/components/schemas/User" })
+    );
+    assert_eq!(
+        schemas["Users"],
+        json!({ "$ref": "#/components/schemas/PageUser" })
+    );
+}
+
+#[test]
+fn generic_instances_can_be_named_differently() {
+    let doc = project(&format!("{GENERICS}{USERS}")).generate(&["--generic-naming", "of"]);
+
+    assert!(doc["components"]["schemas"]
+        .as_object()
+        .unwrap()
+        .contains_key("PageOfUser"));
+}
@@ -0,0 +1,262 @@
+mod common;
+
+use common::{line_of, Project};
+
+const INFO: &str = r#"
+default info Api {
+    title = "API"
+    version = "1.0.0"
+}
+"#;
+
+fn failure(fields: &str) -> String {
+    format!(
+        r#"{INFO}
+path "/failure" {{
+    get failure() {{
+        return 500 response {{ content = Failure }}
+    }}
+}}
+schema Failure {{ {fields} }}
+"#
+    )
+}
+
+fn schema_names(doc: &serde_json::Value) -> Vec<String> {
+    doc["components"]["schemas"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn imports_are_searched_in_include_directories() {
+    let project = Project::new()
+        .file(
+            "main.oai",
+            &format!("import \"errors.oai\"\n{}", failure("error: Error")),
+        )
+        .file("first/errors.oai", "schema Error { code: Int32 }")
+        .file("second/errors.oai", "schema Error { message: String }");
+
+    let doc = project.generate(&["-i", "first", "--include", "second"]);
+    assert!(doc["components"]["schemas"]["Error"]["properties"]["code"].is_object());
+
+    let doc = project.generate(&["-i", "second", "-i", "first"]);
+    assert!(doc["components"]["schemas"]["Error"]["properties"]["message"].is_object());
+}
+
+#[test]
+fn imports_are_searched_in_openapi_gen_path() {
+    let project = Project::new()
+        .file(
+            "main.oai",
+            &format!("import \"errors.oai\"\n{}", failure("error: Error")),
+        )
+        .file("first/errors.oai", "schema Error { code: Int32 }")
+        .file("second/errors.oai", "schema Error { message: String }");
+
+    let separator = if cfg!(windows) { ";" } else { ":" };
+    let mut command = project.command(&["main.oai", "-o", "-", "-f", "json"]);
+    command.env(
+        "OPENAPI_GEN_PATH",
+        format!(
+            "{}{separator}{}",
+            project.dir.join("first").display(),
+            project.dir.join("second").display()
+        ),
+    );
+    let run = common::run(command);
+    assert!(run.success, "{}", run.stderr);
+    let doc: serde_json::Value = serde_json::from_str(&run.stdout).unwrap();
+    assert!(doc["components"]["schemas"]["Error"]["properties"]["code"].is_object());
+
+    // include directories come before the environment
+    let mut command = project.command(&["main.oai", "-o", "-", "-f", "json", "-i", "second"]);
+    command.env("OPENAPI_GEN_PATH", project.dir.join("first"));
+    let run = common::run(command);
+    let doc: serde_json::Value = serde_json::from_str(&run.stdout).unwrap();
+    assert!(doc["components"]["schemas"]["Error"]["properties"]["message"].is_object());
+}
+
+#[test]
+fn missing_import_is_an_error() {
+    let d = Project::new()
+        .file("main.oai", &format!("import \"missing.oai\"\n{INFO}"))
+        .diagnostics(&[]);
+    assert!(d[0].error, "{d:#?}");
+    assert!(d[0].message.contains("missing.oai"), "{d:#?}");
+    assert_eq!(d[0].line, Some(1));
+}
+
+#[test]
+fn namespaced_and_selective_imports() {
+    let doc = Project::new()
+        .file(
+            "main.oai",
+            &format!(
+                "import \"common/errors.oai\" as errors\nimport {{ NotFound }} from \"common/http.oai\"\n{}",
+                failure("error: errors.Error, notFound: NotFound")
+            ),
+        )
+        .file("common/errors.oai", "schema Error { code: Int32 }")
+        .file(
+            "common/http.oai",
+            "schema NotFound { path: String }\nschema Error { status: Int32 }",
+        )
+        .generate(&[]);
+
+    // names defined in more than one file are qualified by the path of the file
+    assert_eq!(
+        schema_names(&doc),
+        [
+            "Failure",
+            "common.errors.Error",
+            "NotFound",
+            "common.http.Error"
+        ]
+    );
+}
+
+#[test]
+fn names_of_files_not_imported_are_not_visible() {
+    let d = Project::new()
+        .file(
+            "main.oai",
+            &format!("import \"a.oai\"\n{}", failure("error: Error")),
+        )
+        .file(
+            "a.oai",
+            "import \"b.oai\" as b\nschema A { error: b.Error }",
+        )
+        .file("b.oai", "schema Error { code: Int32 }")
+        .diagnostics(&[]);
+    assert_eq!(
+        line_of(
+            &d,
+            "`Error` is defined in `b.oai`, which is not imported here"
+        ),
+        13
+    );
+}
+
+#[test]
+fn same_file_names_outside_root_are_qualified_by_namespace() {
+    let doc = Project::new()
+        .file(
+            "app/main.oai",
+            &format!(
+                "import \"../lib-a/errors.oai\" as a\nimport \"../lib-b/errors.oai\" as b\n{}",
+                failure("first: a.Error, second: b.Error")
+            ),
+        )
+        .file("lib-a/errors.oai", "schema Error { code: Int32 }")
+        .file("lib-b/errors.oai", "schema Error { message: String }")
+        .generate_from("app/main.oai", &[]);
+
+    assert_eq!(schema_names(&doc), ["Failure", "a.Error", "b.Error"]);
+}
+
+#[test]
+fn colliding_qualifiers_are_reported() {
+    let project = Project::new()
+        .file(
+            "app/main.oai",
+            &format!(
+                "import \"other.oai\"\nimport \"../lib-a/errors.oai\" as errors\n{}",
+                failure("error: errors.Error")
+            ),
+        )
+        .file(
+            "app/other.oai",
+            "import \"../lib-b/errors.oai\" as errors\nschema OtherFailure { error: errors.Error }",
+        )
+        .file("lib-a/errors.oai", "schema Error { code: Int32 }")
+        .file("lib-b/errors.oai", "schema Error { message: String }");
+
+    let d = project.diagnostics_from("app/main.oai", &[]);
+    let dir = project.dir.display();
+    assert!(
+        d.iter().any(|d| d.message
+            == format!(
+                "`Error` of `{dir}/lib-b/errors.oai` and `{dir}/lib-a/errors.oai` would both be named `errors.Error`"
+            )),
+        "{d:#?}"
+    );
+    assert!(!d.iter().any(|d| d.message.contains("more than once")));
+}
+
+fn cyclic() -> Project {
+    Project::new()
+        .file(
+            "main.oai",
+            &format!(
+                "import \"a.oai\"\nimport \"lib/b.oai\"\n{}",
+                failure("a: A")
+            ),
+        )
+        .file("a.oai", "import \"lib/b.oai\"\nschema A { b: B }")
+        .file("lib/b.oai", "import \"../a.oai\"\nschema B { id: Int }")
+}
+
+#[test]
+fn import_cycles_are_warned_about() {
+    let d = cyclic().diagnostics(&[]);
+    assert_eq!(
+        line_of(&d, "import cycle: `a.oai` -> `lib/b.oai` -> `a.oai`"),
+        1
+    );
+    assert!(d.iter().all(|d| !d.error), "{d:#?}");
+}
+
+#[test]
+fn import_graph_is_printed_as_a_tree() {
+    let run = cyclic().run(&["main.oai", "--print-imports"]);
+    assert!(run.success, "{}", run.stderr);
+    assert_eq!(
+        run.stdout,
+        "main.oai\n\
+         ├── a.oai\n\
+         │   └── lib/b.oai\n\
+         │       └── a.oai (*)\n\
+         └── lib/b.oai (*)\n"
+    );
+}
+
+#[test]
+fn import_graph_is_printed_as_dot() {
+    let run = cyclic().run(&["main.oai", "--print-imports", "dot"]);
+    assert!(run.success, "{}", run.stderr);
+    assert_eq!(
+        run.stdout,
+        r#"digraph imports {
+    file0 [label="main.oai"];
+    file1 [label="a.oai"];
+    file2 [label="lib/b.oai"];
+    file0 -> file1;
+    file0 -> file2;
+    file1 -> file2;
+    file2 -> file1;
+}
+"#
+    );
+}
+
+#[test]
+fn files_outside_the_root_are_printed_with_their_path() {
+    let project = Project::new()
+        .file(
+            "app/main.oai",
+            &format!("import \"errors.oai\"\n{}", failure("error: Error")),
+        )
+        .file("lib/errors.oai", "schema Error { code: Int32 }");
+
+    let run = project.run(&["app/main.oai", "-i", "lib", "--print-imports"]);
+    assert!(run.success, "{}", run.stderr);
+    assert_eq!(
+        run.stdout,
+        format!("main.oai\n└── {}/lib/errors.oai\n", project.dir.display())
+    );
+}
@@ -0,0 +1,119 @@
+use openapi_gen::{DiskProvider, Format, Generator, MemoryProvider};
+
+const INFO: &str = r#"
+default info Api {
+    title = "API"
+    version = "1.0.0"
+}
+"#;
+
+const HEALTH: &str = r#"
+path "/health" {
+    get health() {
+        return 200 response { content = Health }
+    }
+}
+"#;
+
+#[test]
+fn generates_from_a_string() {
+    let output = Generator::new()
+        .source_str(
+            "api.oai",
+            format!("{INFO}{HEALTH}schema Health {{ ok: Bool }}"),
+        )
+        .generate()
+        .unwrap_or_else(|e| panic!("{e}"));
+
+    assert!(output.warnings.is_empty());
+    assert!(output.value.components.schemas.contains_key("Health"));
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.value.to_bytes(Format::Json).unwrap()).unwrap();
+    assert_eq!(json["paths"]["/health"]["get"]["operationId"], "health");
+}
+
+#[test]
+fn warnings_are_returned_with_the_output() {
+    let output = Generator::new()
+        .source_str(
+            "api.oai",
+            format!("{INFO}{HEALTH}schema Health {{ ok: Bool }}\nschema Unused {{ id: Int }}"),
+        )
+        .generate()
+        .unwrap_or_else(|e| panic!("{e}"));
+
+    assert_eq!(output.warnings.len(), 1);
+    assert_eq!(
+        output.render_warnings().lines().take(2).collect::<Vec<_>>(),
+        [
+            "warning: schema `Unused` is never used",
+            "  --> api.oai:13:1"
+        ]
+    );
+}
+
+#[test]
+fn errors_are_rendered_with_their_source() {
+    let error = Generator::new()
+        .source_str("api.oai", format!("{INFO}{HEALTH}"))
+        .generate()
+        .unwrap_err();
+
+    assert!(error.diagnostics[0].is_error());
+    assert_eq!(
+        error.to_string().lines().take(2).collect::<Vec<_>>(),
+        ["error: schema `Health` is not defined", " --> api.oai:8:5"]
+    );
+}
+
+#[test]
+fn imports_are_read_from_memory() {
+    let provider = MemoryProvider::new()
+        .with_file(
+            "/api/main.oai",
+            format!("import \"types/health.oai\"\n{INFO}{HEALTH}"),
+        )
+        .with_file("/api/types/health.oai", "schema Health { ok: Bool }");
+
+    let output = Generator::new()
+        .provider(provider)
+        .source_file("/api/main.oai")
+        .generate()
+        .unwrap_or_else(|e| panic!("{e}"));
+    assert!(output.value.components.schemas.contains_key("Health"));
+}
+
+#[test]
+fn missing_files_in_memory_are_errors() {
+    let error = Generator::new()
+        .provider(MemoryProvider::new().with_file("/api/main.oai", "import \"missing.oai\""))
+        .source_file("/api/main.oai")
+        .generate()
+        .unwrap_err();
+
+    assert!(
+        error.diagnostics[0].message.contains("missing.oai"),
+        "{error}"
+    );
+}
+
+#[test]
+fn memory_falls_back_to_another_provider() {
+    let dir = std::env::temp_dir().join(format!("openapi-gen-library-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("health.oai"), "schema Health { ok: Bool }").unwrap();
+    let main = dir.join("main.oai");
+
+    let result = Generator::new()
+        .provider(
+            MemoryProvider::new()
+                .with_file(&main, format!("import \"health.oai\"\n{INFO}{HEALTH}"))
+                .with_fallback(DiskProvider),
+        )
+        .source_file(&main)
+        .generate();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let output = result.unwrap_or_else(|e| panic!("{e}"));
+    assert!(output.value.components.schemas.contains_key("Health"));
+}